@@ -1,4 +1,32 @@
 from . import types, xarray_backend
-from .omfilesrspy import OmFilePyReader, OmFilePyWriter
+from .omfilesrspy import (
+    ArrayWithAttrs,
+    CompressionType,
+    OmFilePyReader,
+    OmFilePyWriter,
+    OmLazyArray,
+    OmNotAnOmFileError,
+    OmTruncatedFileError,
+    OmUnsupportedVersionError,
+    OmVariable,
+    OmVariableInfo,
+    supported_compressions,
+    supported_dtypes,
+)
 
-__all__ = ["OmFilePyReader", "OmFilePyWriter", "xarray_backend", "types"]
+__all__ = [
+    "ArrayWithAttrs",
+    "CompressionType",
+    "OmFilePyReader",
+    "OmFilePyWriter",
+    "OmLazyArray",
+    "OmNotAnOmFileError",
+    "OmTruncatedFileError",
+    "OmUnsupportedVersionError",
+    "OmVariable",
+    "OmVariableInfo",
+    "supported_compressions",
+    "supported_dtypes",
+    "xarray_backend",
+    "types",
+]
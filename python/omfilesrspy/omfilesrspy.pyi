@@ -1,64 +1,434 @@
-from typing import Tuple, Union
+from typing import Any, Iterable, Iterator, NamedTuple, Tuple, Union, overload
 
 import numpy as np
 import numpy.typing as npt
 
 from .types import BasicSelection
 
+class OmNotAnOmFileError(Exception):
+    """Raised when a file is empty or does not start with the om file magic number."""
+
+class OmTruncatedFileError(Exception):
+    """Raised when a file has a valid om header but its content is incomplete."""
+
+class ArrayWithAttrs(NamedTuple):
+    """Array data returned together with the scalar attributes of its variable."""
+
+    array: npt.NDArray[np.generic]
+    attrs: dict[str, Any]
+
+class OmUnsupportedVersionError(Exception):
+    """Raised when a file was written with a newer om format version than this library supports."""
+
+class CompressionType:
+    """
+    Compression of an array. The integer values match the compression ids of the file format.
+
+    Like an `IntEnum`, members compare equal to and hash like their integer value. They do
+    not compare equal to their name, use `str()` to get the name that
+    `OmFilePyWriter.write_array` also accepts, e.g. "pfor_delta_2d".
+    """
+
+    PFOR_DELTA_2D_INT16: "CompressionType"
+    FPX_XOR_2D: "CompressionType"
+    PFOR_DELTA_2D: "CompressionType"
+    PFOR_DELTA_2D_INT16_LOGARITHMIC: "CompressionType"
+    NONE: "CompressionType"
+
+    def __int__(self) -> int: ...
+    def __str__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class OmVariable:
+    """A variable written to an om file, identified by its offset and size."""
+
+    @property
+    def name(self) -> str: ...
+    @property
+    def offset(self) -> int: ...
+    @property
+    def size(self) -> int: ...
+    @property
+    def chunks(self) -> list[int] | None:
+        """Chunk dimensions of a written array, None for scalars."""
+        ...
+    def __eq__(self, other: object) -> bool:
+        """Variables are equal if they have the same offset and size, regardless of their name."""
+        ...
+    def __hash__(self) -> int: ...
+
+class OmVariableInfo:
+    """Location and type information of a variable, see `OmFilePyReader.get_flat_variable_info`."""
+
+    @property
+    def name(self) -> str: ...
+    @property
+    def offset(self) -> int: ...
+    @property
+    def size(self) -> int: ...
+    @property
+    def data_type(self) -> str:
+        """Numpy dtype name of scalars and arrays, "str" for strings and "group" for groups."""
+        ...
+    @property
+    def compression(self) -> str | None: ...
+    @property
+    def scale_factor(self) -> float | None: ...
+    @property
+    def add_offset(self) -> float | None: ...
+    @property
+    def dimensions(self) -> list[int]: ...
+    @property
+    def chunk_dimensions(self) -> list[int] | None: ...
+    @property
+    def is_scalar(self) -> bool: ...
+
 class OmFilePyWriter:
     """A Python wrapper for the Rust OmFileWriter implementation."""
 
-    def __init__(self, file_path: str) -> None:
+    def __init__(
+        self, file_path: str, metadata: dict[str, Union[str, int, float]] | None = None, atomic: bool = False
+    ) -> None:
         """
         Initialize an OmFilePyWriter.
 
         Args:
             file_path: Path where the .om file will be created
+            metadata: Optional provenance metadata written as scalar children of the root variable.
+                      Other variables and their uses as children do not get it.
+                      Supported keys: "_created_by", "_created_at", "_om_version"
+            atomic: Write to `<file_path>.tmp.<pid>` and rename it to `file_path` in `close`,
+                    so readers never see a partially written file. If `close` is not called,
+                    the temporary file is left in place. (default: False)
 
         Raises:
             OSError: If the file cannot be created
+            ValueError: If an unsupported metadata key is given
+        """
+        ...
+
+    @staticmethod
+    def in_memory(metadata: dict[str, Union[str, int, float]] | None = None) -> "OmFilePyWriter":
+        """
+        Create a writer which builds the file in memory instead of on disk.
+
+        Args:
+            metadata: Optional provenance metadata, as for the constructor
+
+        Returns:
+            OmFilePyWriter: Writer whose file is returned by `to_bytes`
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """
+        Close a writer created with `in_memory` and return the complete file.
+
+        The result can be opened with `OmFilePyReader.from_bytes`. Calling it again
+        after the writer is closed returns the same bytes.
+
+        Raises:
+            ValueError: If the writer writes to a file
+            RuntimeError: If no array or group was written and no root was set
+        """
+        ...
+
+    def get_bytes(self) -> bytes:
+        """
+        Return the complete file of a closed writer created with `in_memory`.
+
+        Useful after a `with` block, which closes the writer.
+
+        Raises:
+            ValueError: If the writer writes to a file or is still open
+        """
+        ...
+
+    def close(self) -> None:
+        """
+        Close the file. Atomic writers rename the temporary file to the target path.
+
+        Writing to a closed writer raises ValueError. Closing twice has no effect.
+        """
+        ...
+
+    @property
+    def closed(self) -> bool:
+        """Whether `close` has been called or a `with` block was left."""
+        ...
+    def set_root(self, variable: OmVariable) -> None:
+        """
+        Make `variable` the root of the file by writing a new trailer pointing to it.
+
+        By default the last written array or group is the root of the file.
+        """
+        ...
+    def __enter__(self) -> "OmFilePyWriter": ...
+    def __exit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool:
+        """
+        Close the writer at the end of a `with` block.
+
+        The last written array is the root of the file. If the block raised, the file is
+        closed without renaming the temporary file of an atomic writer.
+
+        Raises:
+            RuntimeError: If no array or group was written and no root was set in the block
         """
         ...
 
     def write_array(
         self,
-        data: npt.NDArray[
-            Union[
-                np.float32, np.float64, np.int32, np.int64, np.uint32, np.uint64, np.int8, np.uint8, np.int16, np.uint16
-            ]
-        ],
-        chunks: list[int] | tuple[int, ...],
+        data: npt.ArrayLike,
+        chunks: list[int] | tuple[int, ...] | None = None,
         scale_factor: float = 1.0,
         add_offset: float = 0.0,
-        compression: str = "pfor_delta_2d",
-    ) -> None:
+        compression: CompressionType | str = "pfor_delta_2d",
+        name: str = "data",
+        children: list[OmVariable] | None = None,
+        categories: dict[int, str] | None = None,
+        dimensions: list[str] | None = None,
+        fill_value: int | float | None = None,
+        verify: bool = False,
+    ) -> OmVariable:
         """
         Write a numpy array to the .om file with specified chunking and scaling parameters.
 
         Args:
             data: Input array to be written. Supported dtypes are:
-                 float32, float64, int32, int64, uint32, uint64, int8, uint8, int16, uint16
-            chunks: Chunk sizes for each dimension of the array
+                 float32, float64, int32, int64, uint32, uint64, int8, uint8, int16, uint16, float16
+                 float16 arrays are stored as float32 and read back as float16. Use the lossless
+                 "fpx_xor_2d" compression for them, the default quantizes to `1 / scale_factor`.
+                 Array-likes such as pandas or polars Series and Arrow arrays are converted
+                 with `np.asarray`. A DataFrame becomes a 2-D array with shape (rows, columns).
+                 Arrays which are not C-contiguous, e.g. `a.T` or `a[::2]`, are copied to
+                 C order before encoding, which temporarily needs memory for a second copy.
+            chunks: Chunk sizes for each dimension of the array. If not given, chunks of
+                    roughly 65536 elements are chosen which keep the last dimension contiguous.
+                    The chosen chunks are available as `chunks` of the returned variable.
+                    Each chunk size has to be between 1 and the size of its dimension.
             scale_factor: Scale factor for data compression (default: 1.0)
             add_offset: Offset value for data compression (default: 0.0)
-            compression: Compression algorithm to use (default: "pfor_delta_2d")
-                       Supported values: "pfor_delta_2d", "fpx_xor_2d", "pfor_delta_2d_int16", "pfor_delta_2d_int16_logarithmic"
+                        Both are stored as 32 bit floats. For float64 data with a quantizing
+                        compression, an offset whose 32 bit rounding error exceeds half the
+                        quantization step `0.5 / scale_factor` raises a ValueError, as all
+                        decoded values would be shifted by it.
+            compression: Compression algorithm to use as `CompressionType` or by name (default: "pfor_delta_2d")
+                       Supported names: "pfor_delta_2d", "fpx_xor_2d", "pfor_delta_2d_int16", "pfor_delta_2d_int16_logarithmic", "none"
+                       "pfor_delta_2d_int16_logarithmic" stores `log10(1 + x)` and is meant for
+                       non-negative data such as precipitation. Negative values raise a ValueError.
+            name: Name of the variable (default: "data")
+            children: Variables to attach as children, e.g. scalar attributes (default: None)
+            categories: Labels of the values of an integer array, stored as a JSON string
+                        child named "categories" (default: None)
+            dimensions: Names of the dimensions, stored as a JSON string child named
+                        "_ARRAY_DIMENSIONS" (default: None)
+            fill_value: Value marking missing data, stored as a scalar child named "_FillValue"
+                        and returned by `OmFilePyReader.fill_value` (default: None)
+            verify: Read the written array back and compare it with the input within the
+                    quantization tolerance of the compression. Large arrays are only compared
+                    in their first chunk. (default: False)
+
+        Returns:
+            OmVariable referencing the written array. The last written array is the root of the file.
 
         Raises:
             PyValueError: If the data type is unsupported or if parameters are invalid
             OSError: If there's an error writing to the file
+            RuntimeError: If `verify` is set and the data read back differs from the input
+        """
+        ...
+
+    def write_array_with_attrs(
+        self,
+        data: npt.ArrayLike,
+        attrs: dict[str, Union[str, int, float]],
+        chunks: list[int] | tuple[int, ...] | None = None,
+        scale_factor: float = 1.0,
+        add_offset: float = 0.0,
+        compression: CompressionType | str = "pfor_delta_2d",
+        name: str = "data",
+        children: list[OmVariable] | None = None,
+    ) -> OmVariable:
+        """
+        Write an array with scalar attributes in one call.
+
+        Each attribute is written with `write_scalar` and attached as a child of the array,
+        so it can be read back with `OmFilePyReader.attrs`. The other arguments are the
+        same as for `write_array`.
+
+        Args:
+            data: Input array to be written
+            attrs: Attribute names mapped to str, int or float values, e.g. {"units": "K"}
+
+        Returns:
+            OmVariable referencing the written array
+
+        Raises:
+            TypeError: If an attribute value is not a str, int or float
+        """
+        ...
+
+    def write_array_from_iter(
+        self,
+        chunk_iter: Iterable[tuple[list[int] | tuple[int, ...], npt.ArrayLike]],
+        dimensions: list[int],
+        chunks: list[int],
+        dtype: str,
+        scale_factor: float = 1.0,
+        add_offset: float = 0.0,
+        compression: CompressionType | str = "pfor_delta_2d",
+        name: str = "data",
+        children: list[OmVariable] | None = None,
+    ) -> OmVariable:
+        """
+        Write an array chunk by chunk, without holding the complete array in memory.
+
+        Args:
+            chunk_iter: Iterable of (chunk_coords, data) pairs. Chunks have to be given in
+                        row-major order of their coordinates, e.g. (0, 0), (0, 1), (1, 0), ...
+                        Each data array has the shape of its chunk, which is smaller at the
+                        upper boundaries of the array.
+            dimensions: Shape of the complete array
+            chunks: Chunk sizes for each dimension of the array, between 1 and the dimension
+            dtype: Numpy dtype name of the data, one of `supported_dtypes()`
+            scale_factor: Scale factor for data compression (default: 1.0)
+            add_offset: Offset value for data compression (default: 0.0). Validated like in
+                        `write_array`, including the precision check for float64 data.
+            compression: Compression algorithm to use as `CompressionType` or by name (default: "pfor_delta_2d")
+            name: Name of the variable (default: "data")
+            children: Variables to attach as children (default: None)
+
+        Returns:
+            OmVariable referencing the written array. The last written array is the root of the file.
+
+        Raises:
+            ValueError: If chunks are missing, out of order or have the wrong shape.
+                        Chunks encoded before the error remain in the file unreferenced.
+            TypeError: If a chunk does not have the given dtype
+        """
+        ...
+
+    def write_scalar(
+        self, value: Union[str, int, float], name: str, children: list[OmVariable] | None = None
+    ) -> OmVariable:
+        """
+        Write a scalar value which can be attached as a child to other variables.
+
+        Args:
+            value: Scalar value to write
+            name: Name of the variable
+            children: Variables to attach as children (default: None)
+
+        Returns:
+            OmVariable referencing the written scalar
+
+        Raises:
+            TypeError: If the value type is unsupported
+        """
+        ...
+
+    def rechunk_from(
+        self,
+        source: "OmFilePyReader",
+        new_chunks: list[int] | tuple[int, ...],
+        name: str | None = None,
+        children: list[OmVariable] | None = None,
+    ) -> OmVariable:
+        """
+        Copy an array with new chunk dimensions, e.g. to optimize it for another access pattern.
+
+        Compression, scale factor and offset of the source are kept. The complete array is
+        held in memory while it is rewritten.
+
+        Args:
+            source: Reader of the array to copy, possibly from another file
+            new_chunks: Chunk sizes for each dimension of the array
+            name: Name of the new variable (default: name of the source)
+            children: Variables to attach as children (default: None)
+
+        Returns:
+            OmVariable referencing the written array
+
+        Raises:
+            ValueError: If the source is not an array
+        """
+        ...
+
+    def write_group(self, name: str, children: list[OmVariable] | None = None) -> OmVariable:
+        """
+        Write a group without data which only holds its children.
+
+        Like arrays, the last written group is the root of the file unless an array is
+        written after it.
+
+        Args:
+            name: Name of the group
+            children: Variables to attach as children (default: None)
+
+        Returns:
+            OmVariable referencing the written group, usable as a child of other variables
+        """
+        ...
+
+    def write_time_array(
+        self,
+        data: npt.NDArray[Union[np.float32, np.float64, np.int32, np.int64]],
+        chunks: list[int] | tuple[int, ...],
+        units: str,
+        calendar: str = "standard",
+        name: str = "time",
+        children: list[OmVariable] | None = None,
+    ) -> OmVariable:
+        """
+        Write a numeric time coordinate with CF `units` and `calendar` attributes.
+
+        Args:
+            data: Numeric time values
+            chunks: Chunk sizes for each dimension of the array
+            units: CF time units, e.g. "days since 1900-01-01"
+            calendar: CF calendar, e.g. "standard", "noleap" or "360_day" (default: "standard")
+            name: Name of the variable (default: "time")
+            children: Additional variables to attach as children (default: None)
+
+        Returns:
+            OmVariable referencing the written array
+
+        Raises:
+            ValueError: If the calendar is unsupported
+        """
+        ...
+
+class OmLazyArray:
+    """Array-like proxy over an OmFilePyReader which only reads the data that is indexed."""
+
+    @property
+    def shape(self) -> Tuple[int, ...]: ...
+    @property
+    def ndim(self) -> int: ...
+    @property
+    def dtype(self) -> np.dtype: ...
+    def __len__(self) -> int:
+        """Length of the first dimension. Raises TypeError for 0-d arrays, like numpy."""
+        ...
+    def __getitem__(self, ranges: BasicSelection) -> npt.NDArray[np.generic]:
+        """
+        Read the indexed portion of the data. Same semantics as `OmFilePyReader.__getitem__`,
+        except that string keys raise TypeError instead of returning child readers.
         """
         ...
 
 class OmFilePyReader:
     """A Python wrapper for the Rust OmFileReader implementation."""
 
-    def __init__(self, file: Union[str, object]) -> None:
+    def __init__(self, file: Union[str, object], squeeze: bool = True) -> None:
         """
         Initialize an OmFilePyReader from a file path or fsspec file object.
 
         Args:
             file: Path to the .om file to read or a fsspec file object
+            squeeze: Remove singleton dimensions from read results. Set to False to always
+                     return arrays with one dimension per stored dimension. (default: True)
 
         Raises:
             PyValueError: If the file cannot be opened or is invalid
@@ -83,30 +453,247 @@ class OmFilePyReader:
             Numpy data type of the data
         """
 
+    def format_details(self) -> dict[str, Union[int, str]]:
+        """
+        Get the format version of the file.
+
+        Returns:
+            Dictionary with the "version" of the file format and its "layout", which is
+            "legacy" for header-only files (version 1 and 2) or "trailer" otherwise
+        """
+        ...
+    def info(self) -> dict[str, Any]:
+        """
+        Summarize this variable in a single JSON serializable dict.
+
+        Returns:
+            Dictionary with the keys "name", "kind" ("array", "scalar" or "group"), "shape",
+            "num_children", "record_size", "dtype", "size" (number of elements), "compression",
+            "scale_factor", "add_offset" and "chunk_dimensions". "record_size" is the size in
+            bytes of the metadata record of the variable, not of its compressed data, or None
+            if it is not known. The last six are None for scalars and groups.
+        """
+        ...
+    def list_children(self) -> list[tuple[str, str]]:
+        """
+        List the direct children of this variable without visiting their descendants.
+
+        Returns:
+            (name, dtype) pairs in child order. The dtype is a numpy dtype name for scalars
+            and arrays, "str" for strings and "group" for groups.
+        """
+        ...
+    def get_child_by_name(self, name: str) -> "OmFilePyReader | None":
+        """
+        Get the direct child with the given name.
+
+        If several children share the name, the first one in child order is returned.
+
+        Returns:
+            OmFilePyReader of the child, or None if there is no child with this name
+        """
+        ...
+    def open_path(self, path: str) -> "OmFilePyReader":
+        """
+        Open a descendant of this variable by its slash separated path, e.g. "group/temperature".
+
+        Only the variables along the path are visited, unlike `get_flat_variable_metadata`.
+
+        Raises:
+            KeyError: If any segment of the path does not exist
+        """
+        ...
+    def get_scalar(self, name: str | None = None) -> Union[int, float, str]:
+        """
+        Get the value of a scalar variable, or of a named scalar child of this variable.
+
+        Args:
+            name: Name of a direct child to read instead of this variable.
+                If several children share the name, the first one is read.
+
+        Returns:
+            Value of the scalar
+
+        Raises:
+            ValueError: If no name is given and the variable is an array or a group
+            KeyError: If there is no child with the given name
+            TypeError: If the named child is an array or a group
+        """
+        ...
+
+    def as_lazy(self) -> OmLazyArray:
+        """
+        Get an array-like proxy which only reads the data that is indexed.
+
+        Returns:
+            OmLazyArray with `shape`, `ndim` and `dtype` forwarding indexing to this reader
+        """
+        ...
+
+    @property
+    def compression(self) -> CompressionType:
+        """Compression of the array, e.g. `CompressionType.PFOR_DELTA_2D_INT16`. Raises ValueError for scalars and groups."""
+        ...
+    @property
+    def fill_value(self) -> int | float | None:
+        """Value marking missing data as passed to `OmFilePyWriter.write_array`, or None."""
+        ...
+    @property
+    def scale_factor(self) -> float:
+        """Scale factor of the array compression. Raises ValueError for scalars and groups."""
+        ...
+    @property
+    def add_offset(self) -> float:
+        """Offset of the array compression. Raises ValueError for scalars and groups."""
+        ...
+    @property
+    def chunk_dimensions(self) -> list[int]:
+        """Chunk size along each dimension. Raises ValueError for scalars and groups."""
+        ...
+    @property
+    def is_group(self) -> bool:
+        """Whether this variable is a group without data, which only holds children."""
+        ...
+    @property
+    def children(self) -> list["OmFilePyReader"]:
+        """
+        Readers of the direct children of this variable, in order.
+
+        The children share the file handle of this reader and no data is read.
+        Empty for variables without children.
+        """
+        ...
+    @property
+    def attrs(self) -> dict[str, Union[int, float, str]]:
+        """Scalar children of this variable, e.g. `units`, mapped from name to value. File metadata is not included."""
+        ...
+
     @classmethod
-    def from_path(cls, path: str) -> "OmFilePyReader":
+    def from_path(
+        cls,
+        path: str,
+        validate: bool = False,
+        squeeze: bool = True,
+        base_offset: int = 0,
+        length: int | None = None,
+    ) -> "OmFilePyReader":
         """
         Create an OmFilePyReader from a file path.
 
         Args:
             path: Path to the .om file to read
+            validate: Run `validate()` on open and raise if any problem is found (default: False)
+            squeeze: Remove singleton dimensions from read results (default: True)
+            base_offset: Offset of an .om file embedded in a larger file (default: 0)
+            length: Length of the embedded .om file, None reads until the end of the file (default: None)
 
         Returns:
             OmFilePyReader instance
+
+        Raises:
+            ValueError: If validation is requested and the file has problems,
+                        or if the embedded range exceeds the file
         """
 
     @classmethod
-    def from_fsspec(cls, file_obj: object) -> "OmFilePyReader":
+    def from_fsspec(cls, file_obj: object, squeeze: bool = True, cache_size: int = 16777216) -> "OmFilePyReader":
         """
         Create an OmFilePyReader from a fsspec file object.
 
+        Reads are served from a cache of 256 KiB blocks with LRU eviction, so the many
+        small reads of the decoder result in few requests to the file system.
+
         Args:
             file_obj: fsspec file object with read, seek methods and fs attribute
+            squeeze: Remove singleton dimensions from read results (default: True)
+            cache_size: Maximum size of the block cache in bytes, 0 disables it (default: 16 MiB)
 
         Returns:
             OmFilePyReader instance
         """
+    @classmethod
+    def from_reader(
+        cls, file_obj: object, size: int | None = None, squeeze: bool = True, cache_size: int = 16777216
+    ) -> "OmFilePyReader":
+        """
+        Create an OmFilePyReader from any readable object with read and seek methods.
 
+        Args:
+            file_obj: Readable object, e.g. an `io.BufferedReader` or `io.BytesIO`
+            size: Size of the file in bytes. Determined by seeking to the end if not given.
+            squeeze: Remove singleton dimensions from read results (default: True)
+            cache_size: Maximum size of the block cache in bytes, 0 disables it, see
+                        `from_fsspec` (default: 16 MiB)
+
+        Returns:
+            OmFilePyReader instance
+        """
+    @classmethod
+    def from_url(cls, url: str, squeeze: bool = True) -> "OmFilePyReader":
+        """
+        Create an OmFilePyReader for a file served over HTTP.
+
+        Every read issues an HTTP Range request for the required bytes, so the server
+        has to support them. The file size is determined with a HEAD request.
+
+        Args:
+            url: URL of the .om file
+            squeeze: Remove singleton dimensions from read results (default: True)
+
+        Returns:
+            OmFilePyReader instance
+
+        Raises:
+            OSError: If the server cannot be reached or does not report the file size
+        """
+    @classmethod
+    def from_bytes(cls, data: Union[bytes, bytearray], squeeze: bool = True) -> "OmFilePyReader":
+        """
+        Create an OmFilePyReader from the contents of an om file held in memory.
+
+        The data is copied, so the reader does not touch the filesystem and is not
+        affected by later changes to a `bytearray`. The copy is owned by the reader and
+        its children and is freed when the last of them is dropped. Files written with
+        `OmFilePyWriter.in_memory` can be opened from the result of `to_bytes`.
+
+        Args:
+            data: Complete contents of an om file
+            squeeze: Remove singleton dimensions from read results (default: True)
+
+        Returns:
+            OmFilePyReader instance
+
+        Raises:
+            TypeError: If data is not bytes or bytearray
+        """
+
+    def __repr__(self) -> str:
+        """
+        Summarize the variable in one line, e.g.
+        `OmFilePyReader(name='temperature', shape=[721, 1440], dtype=float32, compression='pfor_delta_2d', children=2)`.
+        Groups show `group` instead of shape and dtype, scalars show their value.
+        """
+        ...
+    def __str__(self) -> str:
+        """Summarize name, dtype, shape, chunks, compression and children of the variable over multiple lines."""
+        ...
+    def __len__(self) -> int:
+        """Get the number of direct children of this variable."""
+        ...
+    def __iter__(self) -> Iterator["OmFilePyReader"]:
+        """Iterate over readers of the direct children of this variable, in order."""
+        ...
+    @overload
+    def __getitem__(self, key: str) -> "OmFilePyReader":
+        """
+        Get the direct child with the given name.
+
+        Raises:
+            KeyError: If there is no child with this name
+            ValueError: If this variable is a scalar
+        """
+        ...
+    @overload
     def __getitem__(
         self, ranges: BasicSelection
     ) -> npt.NDArray[
@@ -114,11 +701,16 @@ class OmFilePyReader:
     ]:
         """
         Read data from the .om file using numpy-style indexing.
-        Currently only slices with step 1 are supported.
+        Slices with positive steps are supported, negative steps raise NotImplementedError.
 
-        The returned array will have singleton dimensions removed (squeezed).
-        For example, if you index a 3D array with [1,:,2], the result will
-        be a 1D array since dimensions 0 and 2 have size 1.
+        As with numpy basic indexing, axes indexed by an integer are removed from
+        the result while sliced axes are kept, even if they select a single element.
+        For example, indexing a 3D array with [1,:,2] returns a 1D array, while
+        [1:2,:,2] returns a 2D array with a leading axis of length 1.
+        Indexing every axis with an integer returns a 0-d array of the stored dtype,
+        e.g. `reader[1, 1].shape == ()`, where numpy would return a numpy scalar.
+        Use `.item()` or `[()]` to get a Python or numpy scalar.
+        Squeezing can be disabled with `squeeze=False` when creating the reader.
 
         Args:
             ranges: Index expression that can be either a single slice/integer
@@ -130,7 +722,7 @@ class OmFilePyReader:
                    - None/newaxis
 
         Returns:
-            NDArray containing the requested data with squeezed singleton dimensions.
+            NDArray containing the requested data with integer indexed axes removed.
             The data type of the array matches the data type stored in the file
             (int8, uint8, int16, uint16, int32, uint32, int64, uint64, float32, or float64).
 
@@ -139,8 +731,402 @@ class OmFilePyReader:
         """
         ...
 
+    def read(
+        self,
+        ranges: BasicSelection,
+        saturation_policy: str = "keep",
+        view_dtype: str | None = None,
+        with_indices: bool = False,
+        decode_categories: bool = False,
+    ) -> Union[npt.NDArray[np.generic], Tuple[npt.NDArray[np.generic], list[npt.NDArray[np.int64]]]]:
+        """
+        Read data from the .om file using numpy-style indexing, same as `__getitem__`.
+
+        Args:
+            ranges: Index expression, see `__getitem__`
+            saturation_policy: How to return float values that were clamped to the int16 range
+                               during lossy compression. "keep" returns the clamped values,
+                               "nan" replaces them with NaN. Saturation is detected on the
+                               decoded values, so a value exactly at the limit is replaced too.
+                               "nan" supports float32 and float64 arrays and raises a
+                               ValueError for other dtypes. (default: "keep")
+            view_dtype: Reinterpret the bytes of the decoded data as this dtype without
+                        converting values, e.g. "uint32" for the bit patterns of a float32 array.
+                        The itemsize must match the stored dtype. (default: None)
+            with_indices: Additionally return the integer indices of every returned axis,
+                          i.e. `arange(start, stop)` of the axis range. Axes removed by
+                          squeezing have no index array. (default: False)
+            decode_categories: Return an object array with the category label of each value.
+                               Values without a category become None. (default: False)
+
+        Returns:
+            NDArray containing the requested data with squeezed singleton dimensions,
+            or a tuple (data, indices) if `with_indices` is set.
+
+        Raises:
+            ValueError: If the requested ranges, the saturation policy or the view dtype are invalid
+        """
+        ...
+
+    def read_into(
+        self,
+        out: npt.NDArray[np.generic],
+        ranges: BasicSelection,
+        nan_replacement: int | None = None,
+        into_offset: list[int] | None = None,
+    ) -> None:
+        """
+        Read data from the .om file into a preallocated numpy array.
+
+        If `out` has the stored dtype and exactly the shape of the requested data, the data
+        is decoded directly into its memory without allocating a temporary array. Otherwise the
+        dtype of `out` may differ from the stored dtype, in which case the values are cast
+        while copying. Casts that may lose information emit a RuntimeWarning.
+
+        Args:
+            out: Writable, C-contiguous integer or float array. Without `into_offset` it must
+                 have the same shape as the (squeezed) requested data
+            ranges: Index expression, see `__getitem__`
+            nan_replacement: Value used for NaN positions when casting float data to an
+                             integer `out` array (default: None)
+            into_offset: Position in `out` where the requested data is written, one entry
+                         per dimension of `out` (default: None)
+
+        Raises:
+            TypeError: If `out` does not have an integer or float dtype
+            IndexError: If the requested data does not fit into `out` at `into_offset`
+            ValueError: If `out` is not C-contiguous, if the shape of `out` does not match
+                        the requested data, or if float data containing NaN is cast to
+                        integers without a `nan_replacement`
+        """
+        ...
+
     def init_from_offset_size(self, offset: int, size: int) -> "OmFilePyReader":
         """Initialize a new OmFilePyReader from an offset and size in an existing file."""
 
     def get_flat_variable_metadata(self) -> dict[str, tuple[int, int]]:
         """Get a mapping of variable names to their file offsets and sizes."""
+
+    def get_flat_variable_info(self) -> dict[str, OmVariableInfo]:
+        """
+        Get a mapping of variable paths to their location, type, shape and compression.
+
+        The keys are the same as for `get_flat_variable_metadata`. Compression fields are None
+        for scalars and groups.
+        """
+
+    def cache_stats(self) -> dict[str, int]:
+        """
+        Get the effectiveness counters of the backend caches.
+
+        Returns:
+            Dictionary with the keys "hits", "misses", "entries" and "bytes".
+            All values are zero if caching is disabled.
+        """
+        ...
+    def set_io_hints(self, io_size_max: int | None = None, io_size_merge: int | None = None) -> None:
+        """
+        Set the I/O coalescing of reads of this variable.
+
+        Reads are split into requests of at most `io_size_max` bytes, and requests separated
+        by less than `io_size_merge` bytes are merged into one. For local files the defaults
+        are a good fit. For high-latency remote storage, values of about 1-16 MiB for
+        `io_size_max` and 64 KiB-1 MiB for `io_size_merge` reduce the number of requests
+        at the cost of reading some unneeded bytes. Children opened afterwards inherit the hints.
+
+        Args:
+            io_size_max: Maximum size of a single read in bytes (default: 65536)
+            io_size_merge: Maximum gap in bytes between two merged reads (default: 512)
+
+        Raises:
+            ValueError: If io_size_max is zero
+        """
+        ...
+    def with_cache(self, max_bytes: int) -> "OmFilePyReader":
+        """
+        Open the root variable of this file with a cache of recently read byte ranges.
+
+        Repeated reads of the same chunks, e.g. time series of the same region, are served
+        from memory instead of the file. Least recently used ranges are evicted once more
+        than `max_bytes` are held. The cache is shared by all children of the returned reader.
+
+        Args:
+            max_bytes: Maximum number of bytes held in the cache
+
+        Returns:
+            OmFilePyReader: Reader of the root variable using the cache
+        """
+        ...
+    def was_deleted(self) -> bool:
+        """
+        Check whether the file was deleted or replaced since the reader was opened.
+
+        Only memory mapped local files are checked, other sources always return False.
+        The reader stays usable, as the memory mapping keeps the old data alive.
+        """
+        ...
+    def release_prefetch(self) -> None:
+        """
+        Drop any prefetch buffers and cached blocks held by the backend.
+
+        The reader stays usable for data reads afterwards. This is a no-op for memory mapped files.
+        """
+        ...
+    def histogram(
+        self, ranges: BasicSelection, bins: int | None = None, range: Tuple[float, float] | None = None
+    ) -> Tuple[npt.NDArray[np.uint64], npt.NDArray[np.float64]]:
+        """
+        Compute a histogram of the values in the requested ranges without returning them to Python.
+
+        NaN values are ignored. All bins are half-open except the last one, like `numpy.histogram`.
+
+        Args:
+            ranges: Index or slice object specifying the ranges to read
+            bins: Number of equal-width bins. If not given, integer arrays spanning at most
+                  256 values are counted per value, all other arrays use 10 bins.
+            range: Lower and upper edge of the bins. Defaults to the minimum and maximum value.
+
+        Returns:
+            Tuple (counts, bin_edges)
+
+        Raises:
+            ValueError: If the range is invalid, or no range is given and the values include infinity
+        """
+        ...
+    def reduce_minmax(self, ranges: BasicSelection) -> Tuple[float, float, float]:
+        """
+        Compute the minimum, maximum and mean of the values in the requested ranges
+        without returning them to Python. Only float arrays are supported.
+
+        NaN values are ignored, like `numpy.nanmin`, `numpy.nanmax` and `numpy.nanmean`.
+
+        Args:
+            ranges: Index or slice object specifying the ranges to read
+
+        Returns:
+            Tuple (min, max, mean). All three are NaN if the ranges contain only NaN.
+
+        Raises:
+            ValueError: If the array does not hold float values
+        """
+        ...
+    def read_all_scalars(self) -> dict[str, Union[int, float, str]]:
+        """
+        Read all scalars in the tree below and including this variable.
+
+        Returns:
+            Slash separated paths, as returned by `variable_paths`, mapped to the values.
+            Strings become str, integers int and floating point values float.
+        """
+        ...
+    def to_tree(self) -> dict[str, dict[str, Any]]:
+        """
+        Get the structure of this variable and its descendants as nested dicts.
+
+        No array data is read. The result maps the name of this variable to a node
+        `{"dtype": str, "shape": list[int], "children": {name: node, ...}}`.
+        Groups have the dtype "group" and string scalars "str", scalars have an empty shape.
+        """
+        ...
+    def describe(self) -> str:
+        """
+        Get an indented overview of this variable and all its descendants, e.g.
+
+            / (group)
+              temperature (float32, shape=[721, 1440], chunks=[64, 64], pfor_delta_2d_int16)
+                units (scalar, 'K')
+
+        Arrays show their dtype, shape, chunks and compression, scalars their value.
+        Children are listed in index order. An unnamed root is shown as "/".
+        """
+        ...
+    def variable_paths(self) -> list[str]:
+        """Get the sorted paths of all variables in the tree, same as the keys of `get_flat_variable_metadata`."""
+
+    def validate(self) -> list[str]:
+        """
+        Check the file structure and the metadata of all variables for consistency.
+
+        All variables below this one are checked and every problem is reported, so a
+        damaged file shows all its issues at once. The checks are:
+
+        - the metadata of every variable lies within the file and can be decoded
+        - arrays have as many chunk dimensions as dimensions and no chunk dimension is 0
+
+        The magic number and format version are verified when the file is opened, which
+        raises `OmNotAnOmFileError` or `OmUnsupportedVersionError` instead.
+        The look-up tables of the chunks are not checked.
+
+        Returns:
+            List of problems found, empty for a healthy file
+        """
+        ...
+
+    def chunk_grid(self) -> Tuple[int, ...]:
+        """
+        Get the number of chunks along each dimension, i.e. `ceil(dimension / chunk)` per axis.
+
+        Raises:
+            ValueError: If the variable is not an array
+        """
+        ...
+
+    def chunk_count(self) -> int:
+        """
+        Get the total number of chunks of the array.
+
+        Raises:
+            ValueError: If the variable is not an array
+        """
+        ...
+
+    def read_points(self, points: list[tuple[int, ...]]) -> npt.NDArray[np.generic]:
+        """
+        Read the values at scattered points, e.g. for station extraction from gridded data.
+
+        Points are grouped by the chunk containing them, so every chunk is decoded only once.
+
+        Args:
+            points: Index tuples with one (possibly negative) index per dimension
+
+        Returns:
+            1-D array with one value per point, in the order of the input points and with
+            the same dtype as `__getitem__`
+
+        Raises:
+            IndexError: If a point has the wrong number of indices or is out of bounds
+        """
+        ...
+
+    def read_ranges(self, ranges: list[BasicSelection]) -> list[npt.NDArray[np.generic]]:
+        """
+        Read several index expressions in a single call, e.g. many time steps of a series.
+
+        This avoids the per-call overhead of repeated `__getitem__` calls. Each expression
+        is still decoded separately, so I/O is not merged across expressions. If the
+        expressions lie close together, a single covering slice may need fewer reads.
+
+        Args:
+            ranges: Index expressions with the same syntax as `__getitem__`
+
+        Returns:
+            One array per index expression, in the order of the input
+
+        Raises:
+            IndexError: If any index expression is out of bounds. Nothing is read in that case.
+        """
+        ...
+
+    def decode_time(self) -> npt.NDArray[np.object_]:
+        """
+        Convert a time coordinate written by `OmFilePyWriter.write_time_array` to datetimes.
+        Requires the `cftime` package. Standard calendars yield Python datetimes,
+        non-standard calendars yield cftime datetimes.
+
+        Raises:
+            ValueError: If the variable has no units or an unsupported calendar
+            ImportError: If cftime is not installed
+        """
+
+    def file_metadata(self) -> dict[str, Union[str, int, float]]:
+        """
+        Get the provenance metadata stored as scalar children of this variable.
+
+        Returns:
+            Dictionary with the recognized keys "_created_by", "_created_at" and "_om_version"
+            that are present in the file. Empty for variables other than the root.
+        """
+    def read_into_torch(self, tensor: Any, ranges: BasicSelection) -> None:
+        """
+        Read data directly into the memory of a preallocated torch tensor.
+
+        Args:
+            tensor: Contiguous CPU `torch.Tensor` with the stored dtype and the shape of the result
+            ranges: Index or slice object specifying the ranges to read
+
+        Raises:
+            ValueError: If the tensor is not on the CPU, not contiguous or has the wrong shape
+            TypeError: If the dtype of the tensor differs from the stored dtype
+        """
+        ...
+    def read_with_attrs(self, ranges: BasicSelection) -> ArrayWithAttrs:
+        """
+        Read data from the variable together with its scalar attributes.
+
+        Args:
+            ranges: Index or slice object specifying the ranges to read
+
+        Returns:
+            Named tuple (array, attrs) where attrs maps the names of all scalar children
+            of this variable, e.g. "units" or "long_name", to their values
+        """
+        ...
+    def read_masked(
+        self, ranges: BasicSelection, fill_value: int | float | None = None
+    ) -> np.ma.MaskedArray:
+        """
+        Read data from the variable as a masked array, e.g. to exclude missing values from means.
+
+        NaN values of float arrays are always masked. Integer arrays cannot hold NaN,
+        so their missing values are only masked if a fill value is given or stored.
+
+        Args:
+            ranges: Index or slice object specifying the ranges to read
+            fill_value: Additional value to mask. Defaults to the stored `fill_value`.
+
+        Returns:
+            Masked array with the same shape as `reader[ranges]`
+        """
+        ...
+    def dimension_names(self) -> list[str]:
+        """
+        Get the names of the dimensions of this array.
+
+        Returns:
+            Stored dimension names, or "dim0", "dim1", ... if the array has none
+        """
+        ...
+    def isel(self, selection: dict[str, Union[int, slice]]) -> npt.NDArray[np.generic]:
+        """
+        Read a selection given by dimension name, similar to `xarray.Dataset.isel`.
+
+        Args:
+            selection: Mapping from dimension name to an integer or slice.
+                       Dimensions which are not part of the selection are read completely.
+
+        Returns:
+            NDArray containing the selected data with squeezed singleton dimensions
+
+        Raises:
+            ValueError: If a dimension name does not exist
+        """
+        ...
+    def categories(self) -> dict[int, str] | None:
+        """
+        Get the value to label map of a categorical array.
+
+        Returns:
+            Dictionary from category value to label, or None if the variable has no categories
+        """
+        ...
+    def compression_info(self) -> dict[str, Union[str, float, bool, None]]:
+        """
+        Describe how the data of this variable is compressed.
+
+        Returns:
+            Dictionary with the keys "compression", "scale_factor", "add_offset", "uniform" and
+            "chunks". All chunks currently share one compression, so "uniform" is always True
+            and "chunks" is None. Both are reserved for reporting per-chunk compression.
+
+        Raises:
+            ValueError: If the variable is a scalar or group
+        """
+        ...
+
+def supported_compressions() -> list[str]:
+    """Get the compression strings accepted by `OmFilePyWriter.write_array`."""
+    ...
+
+def supported_dtypes() -> list[str]:
+    """Get the numpy dtype strings accepted by `OmFilePyWriter.write_array`."""
+    ...
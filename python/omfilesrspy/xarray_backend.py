@@ -41,7 +41,12 @@ class OmDataStore(WritableCFDataStore):
         self.variables_offset_store = self._build_variables_offset_store()
 
     def _build_variables_offset_store(self) -> dict[str, tuple[int, int]]:
-        return self.root_variable.get_flat_variable_metadata()
+        # Scalars and groups, e.g. the _ARRAY_DIMENSIONS children of arrays, are no data variables
+        return {
+            name: (info.offset, info.size)
+            for name, info in self.root_variable.get_flat_variable_info().items()
+            if not info.is_scalar and info.data_type != "group"
+        }
 
     def get_variables(self):
         return FrozenDict((k, self.open_store_variable(k)) for k in self.variables_offset_store)
@@ -61,10 +66,9 @@ class OmDataStore(WritableCFDataStore):
             raise ValueError(f"Failed to read variable {k} at offset {offset}")
 
         backend_array = OmBackendArray(reader=reader)
-        shape = backend_array.reader.shape
 
-        # In om-files dimensions are not named, so we just use dim0, dim1, ...
-        dim_names = [f"dim{i}" for i in range(len(shape))]
+        # Arrays without stored dimension names use dim0, dim1, ...
+        dim_names = reader.dimension_names()
         data = indexing.LazilyIndexedArray(backend_array)
         return Variable(dims=dim_names, data=data, attrs=None, encoding=None, fastpath=True)
 
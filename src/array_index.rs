@@ -4,7 +4,7 @@ use std::ops::Range;
 /// A simplified numpy-like array basic indexing implementation.
 /// Compare https://numpy.org/doc/stable/user/basics.indexing.html.
 /// Supports integer, slice, newaxis and ellipsis indexing.
-/// Slice indexing is also currently limited to step size 1!
+/// Slices with negative steps are currently not supported!
 #[derive(Debug)]
 pub enum IndexType {
     Int(i64),
@@ -20,72 +20,138 @@ pub enum IndexType {
 #[derive(Debug)]
 pub struct ArrayIndex(pub Vec<IndexType>);
 
-impl<'py> FromPyObject<'py> for ArrayIndex {
-    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        fn parse_index(item: &Bound<'_, PyAny>) -> PyResult<IndexType> {
-            if item.is_instance_of::<pyo3::types::PySlice>() {
-                let slice = item.downcast::<pyo3::types::PySlice>()?;
-                let start = slice.getattr("start")?.extract()?;
-                let stop = slice.getattr("stop")?.extract()?;
-                let step = slice.getattr("step")?.extract()?;
-                Ok(IndexType::Slice { start, stop, step })
-            } else if item.is_instance_of::<pyo3::types::PyEllipsis>() {
-                Ok(IndexType::Ellipsis)
-            } else if item.is_none() {
-                Ok(IndexType::NewAxis)
-            } else {
-                Ok(IndexType::Int(item.extract()?))
-            }
+impl IndexType {
+    /// Parses a single python index item.
+    pub fn parse(item: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if item.is_instance_of::<pyo3::types::PySlice>() {
+            let slice = item.downcast::<pyo3::types::PySlice>()?;
+            let start = slice.getattr("start")?.extract()?;
+            let stop = slice.getattr("stop")?.extract()?;
+            let step = slice.getattr("step")?.extract()?;
+            Ok(IndexType::Slice { start, stop, step })
+        } else if item.is_instance_of::<pyo3::types::PyEllipsis>() {
+            Ok(IndexType::Ellipsis)
+        } else if item.is_none() {
+            Ok(IndexType::NewAxis)
+        } else {
+            Ok(IndexType::Int(item.extract()?))
         }
+    }
+}
 
+impl<'py> FromPyObject<'py> for ArrayIndex {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
         if let Ok(tuple) = ob.downcast::<pyo3::types::PyTuple>() {
             let indices = tuple
                 .iter()
-                .map(|idx| parse_index(&idx))
+                .map(|idx| IndexType::parse(&idx))
                 .collect::<PyResult<Vec<_>>>()?;
             Ok(ArrayIndex(indices))
         } else {
-            Ok(ArrayIndex(vec![parse_index(ob)?]))
+            Ok(ArrayIndex(vec![IndexType::parse(ob)?]))
+        }
+    }
+}
+
+/// The contiguous ranges to read for an `ArrayIndex` and the step to apply
+/// to each axis of the decoded array afterwards.
+#[derive(Debug, Clone)]
+pub struct ReadPlan {
+    pub ranges: Vec<Range<u64>>,
+    pub steps: Vec<u64>,
+    /// Axes indexed by an integer. Like numpy, these are removed from the output.
+    pub integer_axes: Vec<bool>,
+}
+
+impl ReadPlan {
+    /// A plan reading the given ranges without striding.
+    pub fn contiguous(ranges: Vec<Range<u64>>) -> Self {
+        let steps = vec![1; ranges.len()];
+        let integer_axes = vec![false; ranges.len()];
+        Self {
+            ranges,
+            steps,
+            integer_axes,
         }
     }
+
+    pub fn is_strided(&self) -> bool {
+        self.steps.iter().any(|&step| step != 1)
+    }
+
+    /// Number of elements selected along each axis.
+    pub fn shape(&self) -> Vec<u64> {
+        self.ranges
+            .iter()
+            .zip(&self.steps)
+            .map(|(range, &step)| (range.end - range.start).div_ceil(step))
+            .collect()
+    }
+
+    /// Shape of the output array. With `squeeze`, integer indexed axes are removed.
+    pub fn output_shape(&self, squeeze: bool) -> Vec<u64> {
+        self.shape()
+            .into_iter()
+            .zip(&self.integer_axes)
+            .filter(|(_, &is_integer)| !squeeze || !is_integer)
+            .map(|(len, _)| len)
+            .collect()
+    }
 }
 
 impl ArrayIndex {
+    /// Returns the ranges to read for callers which do not support strided slices.
     pub fn to_read_range(&self, shape: &Vec<u64>) -> PyResult<Vec<Range<u64>>> {
-        // Input validation
-        if self.0.len() > shape.len() {
-            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
-                "Too many indices for array",
+        let plan = self.to_read_plan(shape)?;
+        if plan.is_strided() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Slice step must be 1",
             ));
         }
+        Ok(plan.ranges)
+    }
 
-        let mut ranges = Vec::new();
-        let mut shape_idx = 0;
-        let mut ellipsis_seen = false;
+    pub fn to_read_plan(&self, shape: &Vec<u64>) -> PyResult<ReadPlan> {
         let explicit_dims: usize = self
             .0
             .iter()
             .filter(|&x| !matches!(x, IndexType::Ellipsis))
             .count();
-        let ellipsis_dims = shape.len().saturating_sub(explicit_dims);
+        let ellipsis_count = self.0.len() - explicit_dims;
+
+        // Input validation
+        if explicit_dims > shape.len() {
+            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
+                "Too many indices for array",
+            ));
+        }
+        if ellipsis_count > 1 {
+            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
+                "An index can only have a single ellipsis ('...')",
+            ));
+        }
+
+        let mut ranges = Vec::new();
+        let mut steps = Vec::new();
+        let mut integer_axes = Vec::new();
+        let mut shape_idx = 0;
+        let ellipsis_dims = shape.len() - explicit_dims;
 
-        for (idx, &dim_size) in self.0.iter().zip(shape.iter()) {
+        for idx in self.0.iter() {
+            // The dimension the index applies to, not its position in the index tuple
+            let dim_size = shape.get(shape_idx).copied().unwrap_or(1);
             match idx {
                 IndexType::Ellipsis => {
-                    if ellipsis_seen {
-                        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                            "Only one ellipsis allowed in index",
-                        ));
-                    }
                     // Add full ranges for all dimensions represented by the ellipsis
                     for _ in 0..ellipsis_dims {
                         ranges.push(Range {
                             start: 0,
                             end: shape[shape_idx],
                         });
+                        steps.push(1);
+                        integer_axes.push(false);
                         shape_idx += 1;
                     }
-                    ellipsis_seen = true;
                 }
                 IndexType::Int(i) => {
                     let normalized_idx = Self::normalize_index(*i, dim_size)?;
@@ -93,16 +159,23 @@ impl ArrayIndex {
                         start: normalized_idx,
                         end: normalized_idx + 1,
                     });
+                    steps.push(1);
+                    integer_axes.push(true);
                     shape_idx += 1;
                 }
                 IndexType::Slice { start, stop, step } => {
-                    if let Some(step) = step {
-                        if *step != 1 {
-                            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                                "Slice step must be 1",
-                            ));
-                        }
+                    let step = step.unwrap_or(1);
+                    if step == 0 {
+                        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                            "Slice step cannot be zero",
+                        ));
                     }
+                    if step < 0 {
+                        return Err(PyErr::new::<pyo3::exceptions::PyNotImplementedError, _>(
+                            "omfiles currently do not support negative slice steps.",
+                        ));
+                    }
+                    let step = step as u64;
 
                     let start_idx = match start {
                         Some(s) => {
@@ -136,16 +209,20 @@ impl ArrayIndex {
                         None => dim_size,
                     };
 
-                    if stop_idx <= start_idx {
-                        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                            "omfiles currently do not support reversed ranges.",
-                        ));
-                    }
-
+                    // Like numpy, a slice with start >= stop selects nothing.
+                    // Strided slices read up to and including their last selected element.
+                    let count = stop_idx.saturating_sub(start_idx).div_ceil(step);
+                    let end_idx = if count == 0 {
+                        start_idx
+                    } else {
+                        start_idx + (count - 1) * step + 1
+                    };
                     ranges.push(Range {
                         start: start_idx,
-                        end: stop_idx,
+                        end: end_idx,
                     });
+                    steps.push(step);
+                    integer_axes.push(false);
                     shape_idx += 1;
                 }
                 IndexType::NewAxis => {
@@ -153,6 +230,8 @@ impl ArrayIndex {
                         start: 0,
                         end: dim_size,
                     });
+                    steps.push(1);
+                    integer_axes.push(false);
                 }
             }
         }
@@ -163,10 +242,16 @@ impl ArrayIndex {
                 start: 0,
                 end: shape[shape_idx],
             });
+            steps.push(1);
+            integer_axes.push(false);
             shape_idx += 1;
         }
 
-        Ok(ranges)
+        Ok(ReadPlan {
+            ranges,
+            steps,
+            integer_axes,
+        })
     }
 
     fn normalize_index(idx: i64, dim_size: u64) -> PyResult<u64> {
@@ -343,6 +428,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_empty_slice() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let shape = vec![10];
+
+            let slice = PySlice::new(py, 5, 2, 1);
+            let slice_tuple = pyo3::types::PyTuple::new(py, &[slice.as_ref()]).unwrap();
+            let index = ArrayIndex::extract_bound(slice_tuple.as_ref()).unwrap();
+            let ranges = index.to_read_range(&shape).unwrap();
+            assert_eq!(ranges[0], Range { start: 5, end: 5 });
+
+            let slice = PySlice::new(py, 5, 2, -1);
+            let slice_tuple = pyo3::types::PyTuple::new(py, &[slice.as_ref()]).unwrap();
+            let index = ArrayIndex::extract_bound(slice_tuple.as_ref()).unwrap();
+            assert!(index.to_read_range(&shape).is_err());
+        });
+    }
+
     #[test]
     #[should_panic]
     fn test_invalid_input() {
@@ -385,4 +490,97 @@ mod tests {
             let _should_fail = index.to_read_range(&shape).unwrap();
         });
     }
+
+    #[test]
+    fn test_ellipsis_edge_cases() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let ellipsis = pyo3::types::PyEllipsis::get(py).into_any();
+            let index_of = |items: &[&Bound<'_, PyAny>]| {
+                let tuple = pyo3::types::PyTuple::new(py, items.iter().copied()).unwrap();
+                ArrayIndex::extract_bound(tuple.as_ref()).unwrap()
+            };
+            let one = 1i64.into_pyobject(py).unwrap().into_any();
+
+            // Trailing ellipsis
+            let ranges = index_of(&[&one, &ellipsis])
+                .to_read_range(&vec![2, 3, 4])
+                .unwrap();
+            assert_eq!(ranges, vec![1..2, 0..3, 0..4]);
+
+            // Ellipsis expanding to no dimension
+            let ranges = index_of(&[&one, &ellipsis, &one])
+                .to_read_range(&vec![2, 3])
+                .unwrap();
+            assert_eq!(ranges, vec![1..2, 1..2]);
+
+            // Ellipsis on a 1-D array
+            let ranges = index_of(&[&ellipsis, &one])
+                .to_read_range(&vec![5])
+                .unwrap();
+            assert_eq!(ranges, vec![1..2]);
+
+            // Two ellipses
+            let error = index_of(&[&ellipsis, &one, &ellipsis])
+                .to_read_range(&vec![2, 3, 4])
+                .unwrap_err();
+            assert!(error.is_instance_of::<pyo3::exceptions::PyIndexError>(py));
+        });
+    }
+
+    #[test]
+    fn test_strided_slice() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let shape = vec![10, 7];
+            let tuple = pyo3::types::PyTuple::new(
+                py,
+                &[
+                    PySlice::new(py, 0, 10, 4).into_any(),
+                    PySlice::new(py, 1, 7, 2).into_any(),
+                ],
+            )
+            .unwrap();
+            let index = ArrayIndex::extract_bound(tuple.as_ref()).unwrap();
+            let plan = index.to_read_plan(&shape).unwrap();
+            // Selects rows 0, 4, 8 and columns 1, 3, 5
+            assert_eq!(plan.ranges, vec![0..9, 1..6]);
+            assert_eq!(plan.steps, vec![4, 2]);
+            assert!(index.to_read_range(&shape).is_err());
+
+            // A step of 1 behaves like a plain slice
+            let index = ArrayIndex::extract_bound(PySlice::new(py, 2, 8, 1).as_ref()).unwrap();
+            let plan = index.to_read_plan(&shape).unwrap();
+            assert!(!plan.is_strided());
+            assert_eq!(index.to_read_range(&shape).unwrap(), vec![2..8, 0..7]);
+
+            let index = ArrayIndex::extract_bound(PySlice::new(py, 8, 2, -1).as_ref()).unwrap();
+            let err = index.to_read_plan(&shape).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyNotImplementedError>(py));
+        });
+    }
+
+    #[test]
+    fn test_output_shape() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let shape = vec![4, 5, 6];
+            let tuple = pyo3::types::PyTuple::new(
+                py,
+                &[
+                    1i64.into_pyobject(py).unwrap().into_any(),
+                    PySlice::new(py, 2, 3, 1).into_any(),
+                ],
+            )
+            .unwrap();
+            let index = ArrayIndex::extract_bound(tuple.as_ref()).unwrap();
+            let plan = index.to_read_plan(&shape).unwrap();
+            // The integer axis is removed, the length 1 slice is kept
+            assert_eq!(plan.output_shape(true), vec![1, 6]);
+            assert_eq!(plan.output_shape(false), vec![1, 1, 6]);
+        });
+    }
 }
@@ -0,0 +1,22 @@
+use pyo3::{prelude::*, types::PyDict};
+
+/// Snapshot of the counters of a backend cache.
+/// Caches keep their counters in atomics and report them through this struct.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+impl CacheStats {
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("hits", self.hits)?;
+        dict.set_item("misses", self.misses)?;
+        dict.set_item("entries", self.entries)?;
+        dict.set_item("bytes", self.bytes)?;
+        Ok(dict)
+    }
+}
@@ -0,0 +1,197 @@
+use crate::cache_stats::CacheStats;
+use omfiles_rs::backend::backends::OmFileReaderBackend;
+use omfiles_rs::errors::OmFilesRsError;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Keeps the bytes of recently read ranges of another backend in memory. Ranges are cached
+/// by their exact `(offset, count)`, which matches the repeated reads of the same chunks
+/// when the same region of a file is queried again.
+///
+/// The least recently used ranges are evicted once more than `max_bytes` are held.
+/// Ranges larger than `max_bytes` are read directly from the underlying backend.
+pub struct CachedBackend<Backend> {
+    backend: Arc<Backend>,
+    max_bytes: u64,
+    cache: Mutex<RangeCache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Cached ranges together with the tick of their last use.
+#[derive(Default)]
+struct RangeCache {
+    ranges: HashMap<(u64, u64), (Vec<u8>, u64)>,
+    bytes: u64,
+    tick: u64,
+}
+
+impl RangeCache {
+    fn get(&mut self, range: (u64, u64)) -> Option<Vec<u8>> {
+        self.tick += 1;
+        let (data, last_used) = self.ranges.get_mut(&range)?;
+        *last_used = self.tick;
+        Some(data.clone())
+    }
+
+    fn insert(&mut self, range: (u64, u64), data: Vec<u8>, max_bytes: u64) {
+        if let Some((previous, _)) = self.ranges.remove(&range) {
+            self.bytes -= previous.len() as u64;
+        }
+        while self.bytes + data.len() as u64 > max_bytes {
+            let Some(&oldest) = self
+                .ranges
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(range, _)| range)
+            else {
+                break;
+            };
+            if let Some((evicted, _)) = self.ranges.remove(&oldest) {
+                self.bytes -= evicted.len() as u64;
+            }
+        }
+        self.tick += 1;
+        self.bytes += data.len() as u64;
+        self.ranges.insert(range, (data, self.tick));
+    }
+}
+
+impl<Backend: OmFileReaderBackend> CachedBackend<Backend> {
+    pub fn new(backend: Arc<Backend>, max_bytes: u64) -> Self {
+        Self {
+            backend,
+            max_bytes,
+            cache: Mutex::new(RangeCache::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn backend(&self) -> &Backend {
+        &self.backend
+    }
+
+    /// Drops all cached ranges. The counters of `cache_stats` are kept.
+    pub fn release_prefetch(&self) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.ranges.clear();
+        cache.bytes = 0;
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        let cache = self.cache.lock().unwrap();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: cache.ranges.len() as u64,
+            bytes: cache.bytes,
+        }
+    }
+}
+
+impl<Backend: OmFileReaderBackend> OmFileReaderBackend for CachedBackend<Backend> {
+    fn count(&self) -> usize {
+        self.backend.count()
+    }
+
+    fn needs_prefetch(&self) -> bool {
+        false
+    }
+
+    fn prefetch_data(&self, offset: usize, count: usize) {
+        self.backend.prefetch_data(offset, count)
+    }
+
+    fn pre_read(&self, offset: usize, count: usize) -> Result<(), OmFilesRsError> {
+        self.backend.pre_read(offset, count)
+    }
+
+    fn get_bytes_owned(&self, offset: u64, count: u64) -> Result<Vec<u8>, OmFilesRsError> {
+        if count > self.max_bytes {
+            return self.backend.get_bytes_owned(offset, count);
+        }
+        if let Some(data) = self.cache.lock().unwrap().get((offset, count)) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(data);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        // The underlying backend is read without holding the lock
+        let data = self.backend.get_bytes_owned(offset, count)?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert((offset, count), data.clone(), self.max_bytes);
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use omfiles_rs::backend::backends::InMemoryBackend;
+    use std::error::Error;
+
+    /// Counts the reads reaching the wrapped backend.
+    struct CountingBackend {
+        backend: InMemoryBackend,
+        reads: AtomicU64,
+    }
+
+    impl OmFileReaderBackend for CountingBackend {
+        fn count(&self) -> usize {
+            self.backend.count()
+        }
+
+        fn needs_prefetch(&self) -> bool {
+            false
+        }
+
+        fn prefetch_data(&self, _offset: usize, _count: usize) {}
+
+        fn pre_read(&self, _offset: usize, _count: usize) -> Result<(), OmFilesRsError> {
+            Ok(())
+        }
+
+        fn get_bytes_owned(&self, offset: u64, count: u64) -> Result<Vec<u8>, OmFilesRsError> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.backend.get_bytes_owned(offset, count)
+        }
+    }
+
+    #[test]
+    fn test_cached_backend() -> Result<(), Box<dyn Error>> {
+        let counting = Arc::new(CountingBackend {
+            backend: InMemoryBackend::new((0..100).collect()),
+            reads: AtomicU64::new(0),
+        });
+        let backend = CachedBackend::new(counting.clone(), 16);
+
+        assert_eq!(backend.get_bytes_owned(10, 4)?, vec![10, 11, 12, 13]);
+        assert_eq!(backend.get_bytes_owned(10, 4)?, vec![10, 11, 12, 13]);
+        assert_eq!(counting.reads.load(Ordering::Relaxed), 1);
+
+        // Evicts the least recently used range once the capacity is exceeded
+        backend.get_bytes_owned(20, 8)?;
+        backend.get_bytes_owned(10, 4)?;
+        backend.get_bytes_owned(40, 8)?;
+        assert_eq!(counting.reads.load(Ordering::Relaxed), 3);
+        backend.get_bytes_owned(10, 4)?;
+        assert_eq!(counting.reads.load(Ordering::Relaxed), 3);
+        backend.get_bytes_owned(20, 8)?;
+        assert_eq!(counting.reads.load(Ordering::Relaxed), 4);
+
+        // Larger reads bypass the cache
+        backend.get_bytes_owned(0, 32)?;
+        backend.get_bytes_owned(0, 32)?;
+        assert_eq!(counting.reads.load(Ordering::Relaxed), 6);
+
+        let stats = backend.cache_stats();
+        assert_eq!((stats.hits, stats.misses), (3, 4));
+        assert!(stats.bytes <= 16);
+
+        Ok(())
+    }
+}
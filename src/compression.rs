@@ -0,0 +1,199 @@
+use omfiles_rs::core::compression::CompressionType;
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyInt, IntoPyObjectExt};
+
+#[derive(Clone, PartialEq)]
+pub enum PyCompressionType {
+    PforDelta2dInt16 = 0,
+    FpxXor2d = 1,
+    PforDelta2d = 2,
+    PforDelta2dInt16Logarithmic = 3,
+    None = 4,
+}
+
+/// Compression of an array, exposed to python as `CompressionType`. The integer values
+/// match the compression ids of the file format. Like an `IntEnum`, members compare equal
+/// to and hash like their integer value. `str()` returns the name accepted by
+/// `OmFilePyWriter.write_array`.
+#[pyclass(frozen, name = "CompressionType")]
+#[derive(Clone)]
+pub struct PyCompression(pub PyCompressionType);
+
+#[pymethods]
+impl PyCompression {
+    #[classattr]
+    #[pyo3(name = "PFOR_DELTA_2D_INT16")]
+    fn pfor_delta_2d_int16() -> Self {
+        Self(PyCompressionType::PforDelta2dInt16)
+    }
+
+    #[classattr]
+    #[pyo3(name = "FPX_XOR_2D")]
+    fn fpx_xor_2d() -> Self {
+        Self(PyCompressionType::FpxXor2d)
+    }
+
+    #[classattr]
+    #[pyo3(name = "PFOR_DELTA_2D")]
+    fn pfor_delta_2d() -> Self {
+        Self(PyCompressionType::PforDelta2d)
+    }
+
+    #[classattr]
+    #[pyo3(name = "PFOR_DELTA_2D_INT16_LOGARITHMIC")]
+    fn pfor_delta_2d_int16_logarithmic() -> Self {
+        Self(PyCompressionType::PforDelta2dInt16Logarithmic)
+    }
+
+    #[classattr]
+    #[pyo3(name = "NONE")]
+    fn none() -> Self {
+        Self(PyCompressionType::None)
+    }
+
+    fn __int__(&self) -> i64 {
+        self.0.clone() as i64
+    }
+
+    fn __str__(&self) -> &'static str {
+        self.0.as_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CompressionType.{}", self.0.as_str().to_uppercase())
+    }
+
+    fn __eq__<'py>(&self, other: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let py = other.py();
+        let equal = if let Ok(other) = other.downcast::<Self>() {
+            other.get().0 == self.0
+        } else if other.is_instance_of::<PyInt>() {
+            other
+                .extract::<i64>()
+                .is_ok_and(|value| value == self.__int__())
+        } else {
+            return Ok(py.NotImplemented().into_bound(py));
+        };
+        equal.into_bound_py_any(py)
+    }
+
+    fn __hash__(&self) -> u64 {
+        self.__int__() as u64
+    }
+}
+
+/// A compression passed from python, either as `CompressionType` member or by its name.
+#[derive(FromPyObject)]
+pub enum CompressionArg {
+    Type(PyCompression),
+    Name(String),
+}
+
+impl CompressionArg {
+    pub fn resolve(self) -> PyResult<PyCompressionType> {
+        match self {
+            CompressionArg::Type(compression) => Ok(compression.0),
+            CompressionArg::Name(name) => PyCompressionType::from_str(&name),
+        }
+    }
+}
+
+impl PyCompressionType {
+    /// All compression types that can be selected from python.
+    const ALL: [PyCompressionType; 5] = [
+        PyCompressionType::PforDelta2dInt16,
+        PyCompressionType::FpxXor2d,
+        PyCompressionType::PforDelta2d,
+        PyCompressionType::PforDelta2dInt16Logarithmic,
+        PyCompressionType::None,
+    ];
+
+    pub fn to_omfilesrs(&self) -> CompressionType {
+        match self {
+            PyCompressionType::PforDelta2dInt16 => CompressionType::PforDelta2dInt16,
+            PyCompressionType::FpxXor2d => CompressionType::FpxXor2d,
+            PyCompressionType::PforDelta2d => CompressionType::PforDelta2d,
+            PyCompressionType::PforDelta2dInt16Logarithmic => {
+                CompressionType::PforDelta2dInt16Logarithmic
+            }
+            PyCompressionType::None => CompressionType::None,
+        }
+    }
+
+    pub fn from_omfilesrs(compression: &CompressionType) -> Self {
+        match compression {
+            CompressionType::PforDelta2dInt16 => PyCompressionType::PforDelta2dInt16,
+            CompressionType::FpxXor2d => PyCompressionType::FpxXor2d,
+            CompressionType::PforDelta2d => PyCompressionType::PforDelta2d,
+            CompressionType::PforDelta2dInt16Logarithmic => {
+                PyCompressionType::PforDelta2dInt16Logarithmic
+            }
+            CompressionType::None => PyCompressionType::None,
+        }
+    }
+
+    /// Whether float values are quantized with `scale_factor` and `add_offset`.
+    pub fn is_quantized(&self) -> bool {
+        matches!(
+            self,
+            PyCompressionType::PforDelta2dInt16
+                | PyCompressionType::PforDelta2d
+                | PyCompressionType::PforDelta2dInt16Logarithmic
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PyCompressionType::PforDelta2dInt16 => "pfor_delta_2d_int16",
+            PyCompressionType::FpxXor2d => "fpx_xor_2d",
+            PyCompressionType::PforDelta2d => "pfor_delta_2d",
+            PyCompressionType::PforDelta2dInt16Logarithmic => "pfor_delta_2d_int16_logarithmic",
+            PyCompressionType::None => "none",
+        }
+    }
+
+    pub fn from_str(s: &str) -> PyResult<Self> {
+        Self::ALL
+            .iter()
+            .find(|compression| compression.as_str() == s)
+            .cloned()
+            .ok_or_else(|| PyValueError::new_err(format!("Unsupported compression type: {}", s)))
+    }
+}
+
+/// Returns the name of a stored compression type.
+pub fn compression_name(compression: &CompressionType) -> &'static str {
+    PyCompressionType::from_omfilesrs(compression).as_str()
+}
+
+/// Returns the compression strings accepted by `OmFilePyWriter.write_array`.
+#[pyfunction]
+pub fn supported_compressions() -> Vec<&'static str> {
+    PyCompressionType::ALL
+        .iter()
+        .map(|compression| compression.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_string_roundtrip() {
+        for name in supported_compressions() {
+            let compression = PyCompressionType::from_str(name).unwrap();
+            assert_eq!(compression.as_str(), name);
+        }
+        assert!(PyCompressionType::from_str("zstd").is_err());
+    }
+
+    #[test]
+    fn test_compression_name() {
+        for compression in PyCompressionType::ALL.iter() {
+            assert_eq!(
+                compression_name(&compression.to_omfilesrs()),
+                compression.as_str()
+            );
+        }
+    }
+}
@@ -27,3 +27,40 @@ pub fn to_numpy_dtype(dtype: &DataType) -> &str {
         DataType::StringArray => unimplemented!("todo string"),
     }
 }
+
+/// Returns true for all array data types.
+pub fn is_array(dtype: &DataType) -> bool {
+    matches!(
+        dtype,
+        DataType::Int8Array
+            | DataType::Uint8Array
+            | DataType::Int16Array
+            | DataType::Uint16Array
+            | DataType::Int32Array
+            | DataType::Uint32Array
+            | DataType::Int64Array
+            | DataType::Uint64Array
+            | DataType::FloatArray
+            | DataType::DoubleArray
+            | DataType::StringArray
+    )
+}
+
+/// Name of the element type of scalars and arrays, e.g. "float32" for both `Float` and
+/// `FloatArray`. Strings are named "str" and groups without data "group".
+pub fn dtype_name(dtype: &DataType) -> &'static str {
+    match dtype {
+        DataType::None => "group",
+        DataType::Int8 | DataType::Int8Array => "int8",
+        DataType::Uint8 | DataType::Uint8Array => "uint8",
+        DataType::Int16 | DataType::Int16Array => "int16",
+        DataType::Uint16 | DataType::Uint16Array => "uint16",
+        DataType::Int32 | DataType::Int32Array => "int32",
+        DataType::Uint32 | DataType::Uint32Array => "uint32",
+        DataType::Int64 | DataType::Int64Array => "int64",
+        DataType::Uint64 | DataType::Uint64Array => "uint64",
+        DataType::Float | DataType::FloatArray => "float32",
+        DataType::Double | DataType::DoubleArray => "float64",
+        DataType::String | DataType::StringArray => "str",
+    }
+}
@@ -1,6 +1,41 @@
-use pyo3::PyErr;
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyFileNotFoundError, PyIOError, PyPermissionError},
+    PyErr,
+};
+use std::io::ErrorKind;
+
+create_exception!(
+    omfilesrspy,
+    OmNotAnOmFileError,
+    PyException,
+    "Raised when a file is empty or does not start with the om file magic number."
+);
+create_exception!(
+    omfilesrspy,
+    OmTruncatedFileError,
+    PyException,
+    "Raised when a file has a valid om header but its content is incomplete."
+);
+create_exception!(
+    omfilesrspy,
+    OmUnsupportedVersionError,
+    PyException,
+    "Raised when a file was written with a newer om format version than this library supports."
+);
 
 /// Utility function to convert an OmFilesRsError to a PyRuntimeError
 pub fn convert_omfilesrs_error(e: omfiles_rs::errors::OmFilesRsError) -> PyErr {
     PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
 }
+
+/// Utility function to convert an io::Error raised while opening `path` to
+/// the matching Python OSError subclass
+pub fn convert_io_error(e: std::io::Error, path: &str) -> PyErr {
+    let message = format!("{}: {}", e, path);
+    match e.kind() {
+        ErrorKind::NotFound => PyFileNotFoundError::new_err(message),
+        ErrorKind::PermissionDenied => PyPermissionError::new_err(message),
+        _ => PyIOError::new_err(message),
+    }
+}
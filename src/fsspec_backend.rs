@@ -1,15 +1,70 @@
+use crate::cache_stats::CacheStats;
 use omfiles_rs::backend::backends::OmFileReaderBackend;
+use omfiles_rs::errors::OmFilesRsError;
 use pyo3::prelude::*;
 use pyo3::Python;
+use std::collections::HashMap;
 use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 
+/// Size of the aligned blocks cached by `FsSpecBackend`.
+const BLOCK_SIZE: u64 = 256 * 1024;
+
+/// Default size of the block cache of `FsSpecBackend` in bytes.
+pub const DEFAULT_CACHE_SIZE: u64 = 64 * BLOCK_SIZE;
+
+/// Backend reading through a Python file object. Besides fsspec files, any object with
+/// `read` and `seek` methods is supported via `FsSpecBackend::from_file_like`.
+///
+/// Reads are served from a cache of aligned blocks with LRU eviction, so the many small
+/// reads of the decoder result in few calls to the file object. Adjacent missing blocks
+/// are fetched with a single `read`.
 pub struct FsSpecBackend {
     py_file: PyObject,
     file_size: u64,
+    /// Maximum number of cached blocks, zero disables the cache
+    max_blocks: u64,
+    cache: Mutex<BlockCache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Cached blocks by block index together with the tick of their last use.
+#[derive(Default)]
+struct BlockCache {
+    blocks: HashMap<u64, (Vec<u8>, u64)>,
+    tick: u64,
+}
+
+impl BlockCache {
+    fn get(&mut self, block: u64) -> Option<Vec<u8>> {
+        self.tick += 1;
+        let (data, last_used) = self.blocks.get_mut(&block)?;
+        *last_used = self.tick;
+        Some(data.clone())
+    }
+
+    fn insert(&mut self, block: u64, data: Vec<u8>, max_blocks: u64) {
+        while !self.blocks.contains_key(&block) && self.blocks.len() as u64 >= max_blocks {
+            let Some(&oldest) = self
+                .blocks
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(block, _)| block)
+            else {
+                break;
+            };
+            self.blocks.remove(&oldest);
+        }
+        self.tick += 1;
+        self.blocks.insert(block, (data, self.tick));
+    }
 }
 
 impl FsSpecBackend {
-    pub fn new(open_file: PyObject) -> PyResult<Self> {
+    /// `cache_size` is the maximum number of bytes held in the block cache, zero disables it.
+    pub fn new(open_file: PyObject, cache_size: u64) -> PyResult<Self> {
         let size = Python::with_gil(|py| -> PyResult<u64> {
             let fs = open_file.bind(py).getattr("fs")?;
             let path = open_file.bind(py).getattr("path")?;
@@ -17,39 +72,120 @@ impl FsSpecBackend {
             Ok(size)
         })?;
 
-        Ok(Self {
-            py_file: open_file.into(),
-            file_size: size,
-        })
+        Ok(Self::with_cache(open_file, size, cache_size))
     }
-}
 
-impl OmFileReaderBackend for FsSpecBackend {
-    fn count(&self) -> usize {
-        self.file_size as usize
+    /// Wraps a generic readable Python object that only provides `read` and `seek`.
+    /// If `size` is not given, it is determined by seeking to the end of the object.
+    pub fn from_file_like(
+        file_obj: PyObject,
+        size: Option<u64>,
+        cache_size: u64,
+    ) -> PyResult<Self> {
+        let size = match size {
+            Some(size) => size,
+            None => Python::with_gil(|py| -> PyResult<u64> {
+                let file = file_obj.bind(py);
+                let size = file.call_method1("seek", (0, 2))?.extract::<u64>()?;
+                file.call_method1("seek", (0,))?;
+                Ok(size)
+            })?,
+        };
+
+        Ok(Self::with_cache(file_obj, size, cache_size))
     }
 
-    fn needs_prefetch(&self) -> bool {
-        false
+    fn with_cache(py_file: PyObject, file_size: u64, cache_size: u64) -> Self {
+        Self {
+            py_file,
+            file_size,
+            max_blocks: cache_size.div_ceil(BLOCK_SIZE),
+            cache: Mutex::new(BlockCache::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
     }
 
-    fn prefetch_data(&self, _offset: usize, _count: usize) {
-        // No-op for now
+    /// Drops all cached blocks. The counters of `cache_stats` are kept.
+    pub fn release_prefetch(&self) {
+        self.cache.lock().unwrap().blocks.clear();
     }
 
-    fn pre_read(
-        &self,
-        _offset: usize,
-        _count: usize,
-    ) -> Result<(), omfiles_rs::errors::OmFilesRsError> {
-        Ok(())
+    pub fn cache_stats(&self) -> CacheStats {
+        let cache = self.cache.lock().unwrap();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: cache.blocks.len() as u64,
+            bytes: cache
+                .blocks
+                .values()
+                .map(|(data, _)| data.len() as u64)
+                .sum(),
+        }
     }
 
-    fn get_bytes_owned(
-        &self,
-        offset: u64,
-        count: u64,
-    ) -> Result<Vec<u8>, omfiles_rs::errors::OmFilesRsError> {
+    /// Whether a read is served through the block cache. Reads larger than the
+    /// cache or beyond the end of the file go directly to the file object.
+    fn is_cached(&self, offset: u64, count: u64) -> bool {
+        count > 0
+            && offset
+                .checked_add(count)
+                .is_some_and(|end| end <= self.file_size)
+            && (offset + count - 1) / BLOCK_SIZE - offset / BLOCK_SIZE < self.max_blocks
+    }
+
+    /// Returns the blocks `first..=last`, reading missing blocks from the file object.
+    /// The cache is not locked while Python is called, as `read` may release the GIL.
+    fn cached_blocks(&self, first: u64, last: u64) -> Result<Vec<Vec<u8>>, OmFilesRsError> {
+        let mut blocks = {
+            let mut cache = self.cache.lock().unwrap();
+            (first..=last)
+                .map(|block| cache.get(block))
+                .collect::<Vec<_>>()
+        };
+        let hits = blocks.iter().filter(|block| block.is_some()).count() as u64;
+        self.hits.fetch_add(hits, Ordering::Relaxed);
+        self.misses
+            .fetch_add(blocks.len() as u64 - hits, Ordering::Relaxed);
+
+        let mut fetched = Vec::new();
+        let mut index = 0;
+        while index < blocks.len() {
+            if blocks[index].is_some() {
+                index += 1;
+                continue;
+            }
+            // Coalesce a run of missing blocks into a single read
+            let run_start = index;
+            while index < blocks.len() && blocks[index].is_none() {
+                index += 1;
+            }
+            let start = (first + run_start as u64) * BLOCK_SIZE;
+            let end = ((first + index as u64) * BLOCK_SIZE).min(self.file_size);
+            let data = self.read_uncached(start, end - start)?;
+            for (offset, chunk) in data.chunks(BLOCK_SIZE as usize).enumerate() {
+                let block = run_start + offset;
+                blocks[block] = Some(chunk.to_vec());
+                fetched.push((first + block as u64, chunk.to_vec()));
+            }
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        for (block, data) in fetched {
+            cache.insert(block, data, self.max_blocks);
+        }
+        blocks
+            .into_iter()
+            .map(|block| {
+                block.ok_or_else(|| {
+                    OmFilesRsError::DecoderError("File object returned too few bytes".to_string())
+                })
+            })
+            .collect()
+    }
+
+    fn read_uncached(&self, offset: u64, count: u64) -> Result<Vec<u8>, OmFilesRsError> {
         let bytes = Python::with_gil(|py| -> Result<Vec<u8>, Box<dyn Error>> {
             // Seek to offset
             self.py_file.call_method1(py, "seek", (offset,))?;
@@ -61,17 +197,70 @@ impl OmFileReaderBackend for FsSpecBackend {
             Ok(py_bytes)
         })
         // FIXME: error type
-        .map_err(|e| omfiles_rs::errors::OmFilesRsError::DecoderError(e.to_string()))?;
+        .map_err(|e| OmFilesRsError::DecoderError(e.to_string()))?;
+
+        // Raw `read` calls may return fewer bytes. Caching them would truncate the block.
+        if bytes.len() as u64 != count {
+            return Err(OmFilesRsError::DecoderError(format!(
+                "File object returned {} of {} bytes at offset {}",
+                bytes.len(),
+                count,
+                offset
+            )));
+        }
 
         Ok(bytes)
     }
 }
 
+impl OmFileReaderBackend for FsSpecBackend {
+    fn count(&self) -> usize {
+        self.file_size as usize
+    }
+
+    fn needs_prefetch(&self) -> bool {
+        false
+    }
+
+    fn prefetch_data(&self, offset: usize, count: usize) {
+        // Prefetching is only a hint, errors surface on the actual read
+        let _ = self.pre_read(offset, count);
+    }
+
+    fn pre_read(&self, offset: usize, count: usize) -> Result<(), OmFilesRsError> {
+        let (offset, count) = (offset as u64, count as u64);
+        if self.is_cached(offset, count) {
+            self.cached_blocks(offset / BLOCK_SIZE, (offset + count - 1) / BLOCK_SIZE)?;
+        }
+        Ok(())
+    }
+
+    fn get_bytes_owned(&self, offset: u64, count: u64) -> Result<Vec<u8>, OmFilesRsError> {
+        if !self.is_cached(offset, count) {
+            return self.read_uncached(offset, count);
+        }
+        let first = offset / BLOCK_SIZE;
+        let end = offset + count;
+        let blocks = self.cached_blocks(first, (end - 1) / BLOCK_SIZE)?;
+
+        let mut bytes = Vec::with_capacity(count as usize);
+        for (index, data) in blocks.iter().enumerate() {
+            let block_start = (first + index as u64) * BLOCK_SIZE;
+            let from = offset.saturating_sub(block_start) as usize;
+            let to = ((end - block_start) as usize).min(data.len());
+            bytes.extend_from_slice(&data[from.min(to)..to]);
+        }
+        Ok(bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::create_test_binary_file;
 
     use super::*;
+    use pyo3::ffi::c_str;
+    use pyo3::types::PyModule;
     use std::error::Error;
 
     #[test]
@@ -86,7 +275,7 @@ mod tests {
             let fs = fsspec.call_method1("filesystem", ("file",))?;
             let open_file = fs.call_method1("open", (file_path,))?;
 
-            let backend = FsSpecBackend::new(open_file.into())?;
+            let backend = FsSpecBackend::new(open_file.into(), DEFAULT_CACHE_SIZE)?;
             assert_eq!(backend.file_size, 144);
 
             let bytes = backend.get_bytes_owned(0, 44)?;
@@ -104,4 +293,162 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_file_like_backend() -> Result<(), Box<dyn Error>> {
+        let file_name = "test_file_like_backend.om";
+        let file_path = format!("test_files/{}", file_name);
+        create_test_binary_file!(file_name)?;
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| -> Result<(), Box<dyn Error>> {
+            let io = py.import("io")?;
+            let open_file = io.call_method1("open", (file_path, "rb"))?;
+
+            let backend = FsSpecBackend::from_file_like(open_file.into(), None, 0)?;
+            assert_eq!(backend.file_size, 144);
+
+            let bytes = backend.get_bytes_owned(0, 3)?;
+            assert_eq!(&bytes, &[79, 77, 3]);
+            assert_eq!(backend.cache_stats(), CacheStats::default());
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_cache() -> Result<(), Box<dyn Error>> {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| -> Result<(), Box<dyn Error>> {
+            let data = (0..3 * BLOCK_SIZE)
+                .map(|i| (i % 251) as u8)
+                .collect::<Vec<_>>();
+            let io = py.import("io")?;
+            let buffer = io.call_method1("BytesIO", (pyo3::types::PyBytes::new(py, &data),))?;
+
+            // Room for two blocks
+            let backend = FsSpecBackend::from_file_like(buffer.into(), None, 2 * BLOCK_SIZE)?;
+            let offset = BLOCK_SIZE - 10;
+            assert_eq!(
+                backend.get_bytes_owned(offset, 20)?,
+                data[offset as usize..offset as usize + 20]
+            );
+            assert_eq!(backend.cache_stats().misses, 2);
+            assert_eq!(backend.get_bytes_owned(5, 10)?, data[5..15]);
+            assert_eq!(backend.cache_stats().hits, 1);
+
+            // Reading the third block evicts the least recently used second block
+            let last = 3 * BLOCK_SIZE - 4;
+            assert_eq!(backend.get_bytes_owned(last, 4)?, data[last as usize..]);
+            let stats = backend.cache_stats();
+            assert_eq!((stats.entries, stats.bytes), (2, 2 * BLOCK_SIZE));
+            backend.get_bytes_owned(BLOCK_SIZE, 1)?;
+            assert_eq!(backend.cache_stats().misses, 4);
+
+            backend.release_prefetch();
+            assert_eq!(backend.cache_stats().entries, 0);
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_cache_reduces_python_reads() -> Result<(), Box<dyn Error>> {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| -> Result<(), Box<dyn Error>> {
+            let module = PyModule::from_code(
+                py,
+                c_str!(
+                    "import io
+
+class CountingReader(io.BytesIO):
+    reads = 0
+
+    def read(self, size=-1):
+        self.reads += 1
+        return super().read(size)
+"
+                ),
+                c_str!("counting_reader.py"),
+                c_str!("counting_reader"),
+            )?;
+            let data = (0..2 * BLOCK_SIZE)
+                .map(|i| (i % 251) as u8)
+                .collect::<Vec<_>>();
+
+            // Counts the Python `read` calls of 10 small reads within the first block
+            let count_reads = |cache_size: u64| -> Result<u64, Box<dyn Error>> {
+                let file = module
+                    .getattr("CountingReader")?
+                    .call1((pyo3::types::PyBytes::new(py, &data),))?;
+                let backend =
+                    FsSpecBackend::from_file_like(file.clone().unbind(), None, cache_size)?;
+                let reads_before = file.getattr("reads")?.extract::<u64>()?;
+                for i in 0..10 {
+                    let offset = i * 100;
+                    assert_eq!(
+                        backend.get_bytes_owned(offset, 16)?,
+                        data[offset as usize..offset as usize + 16]
+                    );
+                }
+                Ok(file.getattr("reads")?.extract::<u64>()? - reads_before)
+            };
+
+            assert_eq!(count_reads(0)?, 10);
+            assert_eq!(count_reads(DEFAULT_CACHE_SIZE)?, 1);
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_short_read_is_an_error() -> Result<(), Box<dyn Error>> {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| -> Result<(), Box<dyn Error>> {
+            let module = PyModule::from_code(
+                py,
+                c_str!(
+                    "import io
+
+class ShortReader(io.BytesIO):
+    def read(self, size=-1):
+        return super().read(min(size, 100))
+"
+                ),
+                c_str!("short_reader.py"),
+                c_str!("short_reader"),
+            )?;
+            let data = vec![1u8; 2 * BLOCK_SIZE as usize];
+
+            let open = |cache_size: u64| -> PyResult<FsSpecBackend> {
+                let file = module
+                    .getattr("ShortReader")?
+                    .call1((pyo3::types::PyBytes::new(py, &data),))?;
+                FsSpecBackend::from_file_like(file.unbind(), None, cache_size)
+            };
+
+            let uncached = open(0)?;
+            assert_eq!(uncached.get_bytes_owned(0, 16)?, data[..16]);
+            assert!(uncached.get_bytes_owned(0, 200).is_err());
+
+            // Blocks are read whole, so the short read fails and nothing is cached
+            let cached = open(DEFAULT_CACHE_SIZE)?;
+            assert!(cached.get_bytes_owned(0, 16).is_err());
+            assert!(cached.get_bytes_owned(0, 16).is_err());
+            assert_eq!(cached.cache_stats().entries, 0);
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,125 @@
+use crate::{
+    compression::compression_name,
+    data_type::{dtype_name, is_array},
+};
+use omfiles_rs::{
+    backend::backends::OmFileReaderBackend,
+    core::data_types::DataType,
+    io::{reader::OmFileReader, writer::OmOffsetSize},
+};
+use pyo3::prelude::*;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// A variable written to an om file. It is identified by its offset and size
+/// and can be passed as a child to subsequently written variables.
+#[pyclass]
+#[derive(Clone)]
+pub struct OmVariable {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub offset: u64,
+    #[pyo3(get)]
+    pub size: u64,
+    /// Chunk dimensions of written arrays, `None` for scalars
+    #[pyo3(get)]
+    pub chunks: Option<Vec<u64>>,
+}
+
+impl OmVariable {
+    pub fn new(name: &str, offset_size: &OmOffsetSize) -> Self {
+        Self {
+            name: name.to_string(),
+            offset: offset_size.offset,
+            size: offset_size.size,
+            chunks: None,
+        }
+    }
+
+    pub fn with_chunks(mut self, chunks: Vec<u64>) -> Self {
+        self.chunks = Some(chunks);
+        self
+    }
+
+    pub fn offset_size(&self) -> OmOffsetSize {
+        OmOffsetSize::new(self.offset, self.size)
+    }
+}
+
+#[pymethods]
+impl OmVariable {
+    fn __repr__(&self) -> String {
+        format!(
+            "OmVariable(name='{}', offset={}, size={})",
+            self.name, self.offset, self.size
+        )
+    }
+
+    /// Variables are identified by their location in the file, the name is not compared.
+    fn __eq__(&self, other: &Self) -> bool {
+        (self.offset, self.size) == (other.offset, other.size)
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (self.offset, self.size).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Location and type information of a variable in an om file, as returned by
+/// `OmFilePyReader.get_flat_variable_info`. Compression fields are `None` for
+/// scalars and groups.
+#[pyclass]
+#[derive(Clone)]
+pub struct OmVariableInfo {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub offset: u64,
+    #[pyo3(get)]
+    pub size: u64,
+    #[pyo3(get)]
+    pub data_type: &'static str,
+    #[pyo3(get)]
+    pub compression: Option<&'static str>,
+    #[pyo3(get)]
+    pub scale_factor: Option<f32>,
+    #[pyo3(get)]
+    pub add_offset: Option<f32>,
+    #[pyo3(get)]
+    pub dimensions: Vec<u64>,
+    #[pyo3(get)]
+    pub chunk_dimensions: Option<Vec<u64>>,
+    #[pyo3(get)]
+    pub is_scalar: bool,
+}
+
+impl OmVariableInfo {
+    pub fn new(reader: &OmFileReader<impl OmFileReaderBackend>, offset: u64, size: u64) -> Self {
+        let dtype = reader.data_type();
+        let array = is_array(&dtype);
+        Self {
+            name: reader.get_name().unwrap_or_default(),
+            offset,
+            size,
+            data_type: dtype_name(&dtype),
+            compression: array.then(|| compression_name(&reader.compression())),
+            scale_factor: array.then(|| reader.scale_factor()),
+            add_offset: array.then(|| reader.add_offset()),
+            dimensions: reader.get_dimensions().to_vec(),
+            chunk_dimensions: array.then(|| reader.get_chunk_dimensions().to_vec()),
+            is_scalar: !array && !matches!(dtype, DataType::None),
+        }
+    }
+}
+
+#[pymethods]
+impl OmVariableInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "OmVariableInfo(name='{}', data_type={}, dimensions={:?}, offset={}, size={})",
+            self.name, self.data_type, self.dimensions, self.offset, self.size
+        )
+    }
+}
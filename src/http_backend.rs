@@ -0,0 +1,166 @@
+use omfiles_rs::backend::backends::OmFileReaderBackend;
+use omfiles_rs::errors::OmFilesRsError;
+use pyo3::{exceptions::PyIOError, PyResult};
+use std::io::Read;
+
+/// Backend reading a file served over HTTP. Every read is a separate Range request,
+/// so the server has to support them. The file size is determined once with a HEAD request.
+pub struct HttpBackend {
+    agent: ureq::Agent,
+    url: String,
+    file_size: u64,
+}
+
+impl HttpBackend {
+    pub fn new(url: &str) -> PyResult<Self> {
+        let agent = ureq::Agent::new();
+        let response = agent
+            .head(url)
+            .call()
+            .map_err(|e| PyIOError::new_err(format!("{}: {}", e, url)))?;
+        let file_size = response
+            .header("Content-Length")
+            .and_then(|length| length.parse::<u64>().ok())
+            .ok_or_else(|| {
+                PyIOError::new_err(format!("Server did not report the file size of {}", url))
+            })?;
+
+        Ok(Self {
+            agent,
+            url: url.to_string(),
+            file_size,
+        })
+    }
+}
+
+impl OmFileReaderBackend for HttpBackend {
+    fn count(&self) -> usize {
+        self.file_size as usize
+    }
+
+    fn needs_prefetch(&self) -> bool {
+        false
+    }
+
+    fn prefetch_data(&self, _offset: usize, _count: usize) {
+        // No-op for now
+    }
+
+    fn pre_read(&self, _offset: usize, _count: usize) -> Result<(), OmFilesRsError> {
+        Ok(())
+    }
+
+    fn get_bytes_owned(&self, offset: u64, count: u64) -> Result<Vec<u8>, OmFilesRsError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        // FIXME: error type
+        let error = |message: String| OmFilesRsError::DecoderError(message);
+
+        let response = self
+            .agent
+            .get(&self.url)
+            .set("Range", &format!("bytes={}-{}", offset, offset + count - 1))
+            .call()
+            .map_err(|e| error(format!("{}: {}", e, self.url)))?;
+        // A server ignoring the range would send the whole file
+        if response.status() != 206 {
+            return Err(error(format!(
+                "Server does not support range requests for {}: status {}",
+                self.url,
+                response.status()
+            )));
+        }
+
+        let mut bytes = Vec::with_capacity(count as usize);
+        response
+            .into_reader()
+            .take(count)
+            .read_to_end(&mut bytes)
+            .map_err(|e| error(format!("{}: {}", e, self.url)))?;
+        if bytes.len() as u64 != count {
+            return Err(error(format!(
+                "Expected {} bytes at offset {} of {}, got {}",
+                count,
+                offset,
+                self.url,
+                bytes.len()
+            )));
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_test_binary_file;
+    use std::error::Error;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Serves `data` on a local port, answering HEAD requests and GET requests with a Range header.
+    fn serve(data: Vec<u8>) -> std::io::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/test.om", listener.local_addr()?);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut range = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        let (start, end) = value.trim().split_once('-').unwrap();
+                        range = Some((
+                            start.parse::<usize>().unwrap(),
+                            end.parse::<usize>().unwrap(),
+                        ));
+                    }
+                }
+                let response = match range {
+                    _ if request_line.starts_with("HEAD") => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        data.len()
+                    )
+                    .into_bytes(),
+                    Some((start, end)) => {
+                        let body = &data[start..=end];
+                        let mut response = format!(
+                            "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        )
+                        .into_bytes();
+                        response.extend_from_slice(body);
+                        response
+                    }
+                    None => b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+                };
+                stream.write_all(&response).unwrap();
+            }
+        });
+        Ok(url)
+    }
+
+    #[test]
+    fn test_http_backend() -> Result<(), Box<dyn Error>> {
+        let file_name = "test_http_backend.om";
+        create_test_binary_file!(file_name)?;
+        let data = std::fs::read(format!("test_files/{}", file_name))?;
+        let url = serve(data.clone())?;
+
+        let backend = HttpBackend::new(&url)?;
+        assert_eq!(backend.count(), 144);
+        assert_eq!(backend.get_bytes_owned(0, 3)?, vec![79, 77, 3]);
+        assert_eq!(backend.get_bytes_owned(120, 24)?, data[120..144].to_vec());
+        assert!(backend.get_bytes_owned(0, 0)?.is_empty());
+
+        Ok(())
+    }
+}
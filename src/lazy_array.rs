@@ -0,0 +1,73 @@
+use crate::reader::OmFilePyReader;
+use numpy::PyArrayDescr;
+use pyo3::{
+    exceptions::PyTypeError,
+    prelude::*,
+    types::{PyString, PyTuple},
+};
+
+/// Array-like proxy over an `OmFilePyReader` which only decodes the data that is indexed.
+/// Returned by `OmFilePyReader.as_lazy()`.
+#[pyclass]
+pub struct OmLazyArray {
+    reader: Py<OmFilePyReader>,
+}
+
+impl OmLazyArray {
+    pub fn new(reader: Py<OmFilePyReader>) -> Self {
+        Self { reader }
+    }
+}
+
+#[pymethods]
+impl OmLazyArray {
+    #[getter]
+    fn shape<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        PyTuple::new(py, &self.reader.borrow(py).shape)
+    }
+
+    #[getter]
+    fn ndim(&self, py: Python<'_>) -> usize {
+        self.reader.borrow(py).shape.len()
+    }
+
+    #[getter]
+    fn dtype<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArrayDescr>> {
+        let dtype = self
+            .reader
+            .bind(py)
+            .call_method0("dtype")?
+            .extract::<String>()?;
+        PyArrayDescr::new(py, dtype.as_str())
+    }
+
+    fn __len__(&self, py: Python<'_>) -> PyResult<usize> {
+        match self.reader.borrow(py).shape.first() {
+            Some(&len) => Ok(len as usize),
+            None => Err(PyTypeError::new_err("len() of unsized object")),
+        }
+    }
+
+    fn __getitem__<'py>(
+        &self,
+        py: Python<'py>,
+        key: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        // Unlike the reader, string keys do not select child variables here
+        if key.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err(
+                "OmLazyArray only supports integer, slice, ellipsis and None indices",
+            ));
+        }
+        let reader = self.reader.borrow(py);
+        Ok(reader.read_index(py, key.extract()?)?.into_any())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "OmLazyArray(shape={}, dtype={})",
+            self.shape(py)?,
+            self.dtype(py)?
+        ))
+    }
+}
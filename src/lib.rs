@@ -1,9 +1,17 @@
 use pyo3::prelude::*;
 mod array_index;
+mod cache_stats;
+mod cached_backend;
+mod compression;
 mod data_type;
 mod errors;
 mod fsspec_backend;
+mod hierarchy;
+mod http_backend;
+mod lazy_array;
+mod metadata;
 mod reader;
+mod subrange_backend;
 mod test_utils;
 mod writer;
 
@@ -12,6 +20,25 @@ mod writer;
 fn omfilesrspy<'py>(m: &Bound<'py, PyModule>) -> PyResult<()> {
     m.add_class::<reader::OmFilePyReader>()?;
     m.add_class::<writer::OmFilePyWriter>()?;
+    m.add_class::<hierarchy::OmVariable>()?;
+    m.add_class::<hierarchy::OmVariableInfo>()?;
+    m.add_class::<lazy_array::OmLazyArray>()?;
+    m.add_class::<compression::PyCompression>()?;
+    m.add("ArrayWithAttrs", reader::array_with_attrs_type(m.py())?)?;
+    m.add_function(wrap_pyfunction!(compression::supported_compressions, m)?)?;
+    m.add_function(wrap_pyfunction!(writer::supported_dtypes, m)?)?;
+    m.add(
+        "OmNotAnOmFileError",
+        m.py().get_type::<errors::OmNotAnOmFileError>(),
+    )?;
+    m.add(
+        "OmTruncatedFileError",
+        m.py().get_type::<errors::OmTruncatedFileError>(),
+    )?;
+    m.add(
+        "OmUnsupportedVersionError",
+        m.py().get_type::<errors::OmUnsupportedVersionError>(),
+    )?;
 
     Ok(())
 }
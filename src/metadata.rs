@@ -0,0 +1,32 @@
+/// Names of the root level scalar children that carry provenance metadata of a file.
+/// These are written by `OmFilePyWriter(path, metadata={...})` and read back by
+/// `OmFilePyReader.file_metadata()`.
+pub const FILE_METADATA_KEYS: [&str; 3] = ["_created_by", "_created_at", "_om_version"];
+
+/// Calendars of the CF conventions supported for time coordinates.
+pub const SUPPORTED_CALENDARS: [&str; 9] = [
+    "standard",
+    "gregorian",
+    "proleptic_gregorian",
+    "julian",
+    "noleap",
+    "365_day",
+    "all_leap",
+    "366_day",
+    "360_day",
+];
+
+/// Name of the scalar child holding the JSON encoded value to label map of categorical arrays.
+pub const CATEGORIES_KEY: &str = "categories";
+
+/// Name of the scalar child holding the JSON encoded list of dimension names of an array.
+/// Follows the attribute name used by zarr and xarray.
+pub const DIMENSIONS_KEY: &str = "_ARRAY_DIMENSIONS";
+
+/// Name of the string scalar child holding the numpy dtype of arrays which are stored as a
+/// wider type. float16 arrays are stored as float32 and cast back when read.
+pub const STORED_DTYPE_KEY: &str = "_dtype";
+
+/// Name of the scalar child holding the value that marks missing data in an array.
+/// Follows the attribute name of the CF conventions.
+pub const FILL_VALUE_KEY: &str = "_FillValue";
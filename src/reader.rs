@@ -1,23 +1,72 @@
 use crate::{
-    array_index::ArrayIndex, data_type::to_numpy_dtype, errors::convert_omfilesrs_error,
-    fsspec_backend::FsSpecBackend,
+    array_index::{ArrayIndex, IndexType, ReadPlan},
+    cache_stats::CacheStats,
+    cached_backend::CachedBackend,
+    compression::{compression_name, PyCompression, PyCompressionType},
+    data_type::{dtype_name, is_array, to_numpy_dtype},
+    errors::{
+        convert_io_error, convert_omfilesrs_error, OmNotAnOmFileError, OmTruncatedFileError,
+        OmUnsupportedVersionError,
+    },
+    fsspec_backend::{FsSpecBackend, DEFAULT_CACHE_SIZE},
+    hierarchy::OmVariableInfo,
+    http_backend::HttpBackend,
+    lazy_array::OmLazyArray,
+    metadata::{
+        CATEGORIES_KEY, DIMENSIONS_KEY, FILE_METADATA_KEYS, FILL_VALUE_KEY, STORED_DTYPE_KEY,
+        SUPPORTED_CALENDARS,
+    },
+    subrange_backend::SubRangeBackend,
 };
 use delegate::delegate;
-use num_traits::Zero;
-use numpy::{Element, IntoPyArray, PyArrayMethods, PyUntypedArray};
+use num_traits::{AsPrimitive, Zero};
+use numpy::{
+    ndarray::{Axis, IxDyn, Slice},
+    Element, IntoPyArray, PyArrayDescr, PyArrayDescrMethods, PyArrayDyn, PyArrayMethods,
+    PyUntypedArray, PyUntypedArrayMethods,
+};
 use omfiles_rs::{
-    backend::{backends::OmFileReaderBackend, mmapfile::MmapFile},
-    core::data_types::OmFileArrayDataType,
+    backend::{
+        backends::{InMemoryBackend, OmFileReaderBackend},
+        mmapfile::MmapFile,
+    },
+    core::compression::CompressionType,
+    core::data_types::{DataType, OmFileArrayDataType, OmFileScalarDataType},
     io::{reader::OmFileReader, writer::OmOffsetSize},
 };
-use pyo3::prelude::*;
-use std::{collections::HashMap, sync::Arc};
+use pyo3::{
+    exceptions::{
+        PyImportError, PyIndexError, PyKeyError, PyNotImplementedError, PyRuntimeWarning,
+        PyTypeError, PyValueError,
+    },
+    prelude::*,
+    sync::GILOnceCell,
+    types::{
+        IntoPyDict, PyByteArray, PyBytes, PyDict, PyIterator, PyList, PySlice, PyString, PyTuple,
+    },
+    IntoPyObjectExt,
+};
+use std::{collections::HashMap, ffi::CString, ops::Range, sync::Arc};
 
 #[pyclass]
 pub struct OmFilePyReader {
     reader: OmFileReader<BackendImpl>,
+    /// The backend shared by all variables of the file
+    backend: Arc<BackendImpl>,
+    /// Whether singleton dimensions are removed from read results
+    squeeze: bool,
+    /// I/O coalescing parameters passed to every read of this variable
+    io_hints: IoHints,
     #[pyo3(get)]
-    shape: Vec<u64>,
+    pub(crate) shape: Vec<u64>,
+}
+
+/// Maximum size of a single read and maximum gap merged between two reads in bytes.
+/// `None` uses the defaults of omfiles-rs, 65536 and 512 bytes.
+#[derive(Clone, Copy, Default)]
+struct IoHints {
+    io_size_max: Option<u64>,
+    io_size_merge: Option<u64>,
 }
 
 unsafe impl Send for OmFilePyReader {}
@@ -26,16 +75,17 @@ unsafe impl Sync for OmFilePyReader {}
 #[pymethods]
 impl OmFilePyReader {
     #[new]
-    fn new(source: PyObject) -> PyResult<Self> {
+    #[pyo3(signature = (source, squeeze=true))]
+    fn new(source: PyObject, squeeze: bool) -> PyResult<Self> {
         Python::with_gil(|py| {
             if let Ok(path) = source.extract::<String>(py) {
                 // If source is a string, treat it as a file path
-                Self::from_path(&path)
+                Self::from_path(&path, false, squeeze, 0, None)
             } else {
                 let obj = source.bind(py);
                 if obj.hasattr("read")? && obj.hasattr("seek")? && obj.hasattr("fs")? {
                     // If source has fsspec-like attributes, treat it as a fsspec file object
-                    Self::from_fsspec(source)
+                    Self::from_fsspec(source, squeeze)
                 } else {
                     Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
                         "Input must be either a file path string or a fsspec file object",
@@ -46,21 +96,57 @@ impl OmFilePyReader {
     }
 
     #[staticmethod]
-    fn from_path(file_path: &str) -> PyResult<Self> {
+    #[pyo3(signature = (file_path, validate=false, squeeze=true, base_offset=0, length=None))]
+    fn from_path(
+        file_path: &str,
+        validate: bool,
+        squeeze: bool,
+        base_offset: u64,
+        length: Option<u64>,
+    ) -> PyResult<Self> {
         use omfiles_rs::backend::mmapfile::Mode;
         use std::fs::File;
 
-        let file_handle = File::open(file_path)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
-        let backend = BackendImpl::Mmap(MmapFile::new(file_handle, Mode::ReadOnly)?);
-        let reader = OmFileReader::new(Arc::new(backend)).map_err(convert_omfilesrs_error)?;
-        let shape = reader.get_dimensions().to_vec();
+        let file_handle = File::open(file_path).map_err(|e| convert_io_error(e, file_path))?;
+        let file_size = file_handle
+            .metadata()
+            .map_err(|e| convert_io_error(e, file_path))?
+            .len();
+        // Empty files cannot be memory mapped
+        if file_size == 0 {
+            return Err(OmNotAnOmFileError::new_err(format!(
+                "File {} is empty",
+                file_path
+            )));
+        }
+        let mmap = MmapFile::new(file_handle, Mode::ReadOnly)?;
+        let backend = if base_offset == 0 && length.is_none() {
+            BackendImpl::Mmap(mmap)
+        } else {
+            // The om file is embedded in a larger file
+            BackendImpl::SubRange(SubRangeBackend::new(mmap, base_offset, length)?)
+        };
+        let reader = Self::from_backend(backend, squeeze)?;
 
-        Ok(Self { reader, shape })
+        if validate {
+            let problems = reader.validate()?;
+            if !problems.is_empty() {
+                return Err(PyValueError::new_err(format!(
+                    "File {} failed validation:\n{}",
+                    file_path,
+                    problems.join("\n")
+                )));
+            }
+        }
+
+        Ok(reader)
     }
 
+    /// Reads are cached in aligned blocks, `cache_size` bounds the cache in bytes.
+    /// A `cache_size` of zero disables the cache.
     #[staticmethod]
-    fn from_fsspec(file_obj: PyObject) -> PyResult<Self> {
+    #[pyo3(signature = (file_obj, squeeze=true, cache_size=DEFAULT_CACHE_SIZE))]
+    fn from_fsspec(file_obj: PyObject, squeeze: bool, cache_size: u64) -> PyResult<Self> {
         Python::with_gil(|py| {
             let bound_object = file_obj.bind(py);
 
@@ -73,14 +159,64 @@ impl OmFilePyReader {
                     ));
             }
 
-            let backend = BackendImpl::FsSpec(FsSpecBackend::new(file_obj)?);
-            let reader = OmFileReader::new(Arc::new(backend)).map_err(convert_omfilesrs_error)?;
-            let shape = reader.get_dimensions().to_vec();
+            let backend = BackendImpl::FsSpec(FsSpecBackend::new(file_obj, cache_size)?);
+            Self::from_backend(backend, squeeze)
+        })
+    }
+
+    /// Opens a file from any readable Python object with `read` and `seek` methods,
+    /// e.g. an `io.BufferedReader` or `io.BytesIO`. If `size` is not given it is
+    /// determined by seeking to the end of the object. Reads are cached like in `from_fsspec`.
+    #[staticmethod]
+    #[pyo3(signature = (file_obj, size=None, squeeze=true, cache_size=DEFAULT_CACHE_SIZE))]
+    fn from_reader(
+        file_obj: PyObject,
+        size: Option<u64>,
+        squeeze: bool,
+        cache_size: u64,
+    ) -> PyResult<Self> {
+        Python::with_gil(|py| {
+            let bound_object = file_obj.bind(py);
+
+            if !bound_object.hasattr("read")? || !bound_object.hasattr("seek")? {
+                return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                    "Input must be a readable object with read and seek methods",
+                ));
+            }
 
-            Ok(Self { reader, shape })
+            let backend =
+                BackendImpl::FsSpec(FsSpecBackend::from_file_like(file_obj, size, cache_size)?);
+            Self::from_backend(backend, squeeze)
         })
     }
 
+    /// Opens a file served over HTTP. The server has to support Range requests,
+    /// every read fetches only the required bytes.
+    #[staticmethod]
+    #[pyo3(signature = (url, squeeze=true))]
+    fn from_url(py: Python<'_>, url: &str, squeeze: bool) -> PyResult<Self> {
+        let backend = py.allow_threads(|| HttpBackend::new(url))?;
+        Self::from_backend(BackendImpl::Http(backend), squeeze)
+    }
+
+    /// Opens a file from `bytes` or a `bytearray` held in memory.
+    /// The data is copied, so later changes to a `bytearray` do not affect the reader.
+    #[staticmethod]
+    #[pyo3(signature = (data, squeeze=true))]
+    fn from_bytes(data: &Bound<'_, PyAny>, squeeze: bool) -> PyResult<Self> {
+        let data = if let Ok(bytes) = data.downcast::<PyBytes>() {
+            bytes.as_bytes().to_vec()
+        } else if let Ok(bytes) = data.downcast::<PyByteArray>() {
+            bytes.to_vec()
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "Expected bytes or bytearray, got {}",
+                data.get_type().name()?
+            )));
+        };
+        Self::from_backend(BackendImpl::InMemory(InMemoryBackend::new(data)), squeeze)
+    }
+
     fn get_flat_variable_metadata(&self) -> PyResult<HashMap<String, (u64, u64)>> {
         let metadata = self.reader.get_flat_variable_metadata();
         Ok(metadata
@@ -89,34 +225,1012 @@ impl OmFilePyReader {
             .collect())
     }
 
+    /// Like `get_flat_variable_metadata`, but with the type, shape and compression of
+    /// every variable. Each variable is opened to read its metadata.
+    fn get_flat_variable_info(&self) -> PyResult<HashMap<String, OmVariableInfo>> {
+        self.reader
+            .get_flat_variable_metadata()
+            .into_iter()
+            .map(|(path, offset_size)| {
+                let (offset, size) = (offset_size.offset, offset_size.size);
+                let variable = self
+                    .reader
+                    .init_child_from_offset_size(offset_size)
+                    .map_err(convert_omfilesrs_error)?;
+                Ok((path, OmVariableInfo::new(&variable, offset, size)))
+            })
+            .collect()
+    }
+
+    /// Converts a time coordinate written by `OmFilePyWriter.write_time_array` to datetimes.
+    /// Uses the `units` and `calendar` children of the variable and requires `cftime`.
+    fn decode_time<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let units = find_child(&self.reader, "units")
+            .ok_or_else(|| PyValueError::new_err("Time variable has no units attribute"))?
+            .read_scalar::<String>()
+            .ok_or_else(|| PyValueError::new_err("Time units must be a string"))?;
+        let calendar = match find_child(&self.reader, "calendar") {
+            Some(calendar) => calendar
+                .read_scalar::<String>()
+                .ok_or_else(|| PyValueError::new_err("Time calendar must be a string"))?,
+            None => "standard".to_string(),
+        };
+        if !SUPPORTED_CALENDARS.contains(&calendar.as_str()) {
+            return Err(PyValueError::new_err(format!(
+                "Unsupported calendar: {}. Supported calendars are: {}",
+                calendar,
+                SUPPORTED_CALENDARS.join(", ")
+            )));
+        }
+
+        let cftime = py
+            .import("cftime")
+            .map_err(|_| PyImportError::new_err("decode_time requires the cftime package"))?;
+        let values = self.read_index(py, ArrayIndex(vec![]))?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("calendar", calendar)?;
+        kwargs.set_item("only_use_cftime_datetimes", false)?;
+        cftime.call_method("num2date", (values, units), Some(&kwargs))
+    }
+
+    /// Returns the dimension names stored with the array.
+    /// Arrays without stored names use `dim0`, `dim1`, ... like the xarray backend.
+    fn dimension_names(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        let Some(child) = find_child(&self.reader, DIMENSIONS_KEY) else {
+            return Ok((0..self.shape.len()).map(|i| format!("dim{}", i)).collect());
+        };
+        let json = child
+            .read_scalar::<String>()
+            .ok_or_else(|| PyValueError::new_err("Dimension names must be a string"))?;
+        py.import("json")?
+            .call_method1("loads", (json,))?
+            .extract::<Vec<String>>()
+    }
+
+    /// Reads a selection given as a mapping from dimension name to integer or slice.
+    /// Dimensions which are not part of the selection are read completely.
+    fn isel<'py>(
+        &self,
+        py: Python<'py>,
+        selection: HashMap<String, Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyUntypedArray>> {
+        let names = self.dimension_names(py)?;
+        let mut unknown = selection
+            .keys()
+            .filter(|name| !names.contains(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(PyValueError::new_err(format!(
+                "Dimensions {} do not exist. Available dimensions are: {}",
+                unknown.join(", "),
+                names.join(", ")
+            )));
+        }
+
+        let mut indices = Vec::with_capacity(names.len());
+        for name in &names {
+            let index = match selection.get(name) {
+                Some(item) => IndexType::parse(item)?,
+                None => IndexType::Slice {
+                    start: None,
+                    stop: None,
+                    step: None,
+                },
+            };
+            if matches!(index, IndexType::Ellipsis | IndexType::NewAxis) {
+                return Err(PyTypeError::new_err(format!(
+                    "Selection of dimension {} must be an integer or a slice",
+                    name
+                )));
+            }
+            indices.push(index);
+        }
+        let plan = ArrayIndex(indices).to_read_plan(&self.shape)?;
+        self.read_array(py, plan)
+    }
+
+    /// Returns the value to label map of a categorical array, or None if it has no categories.
+    fn categories<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(child) = find_child(&self.reader, CATEGORIES_KEY) else {
+            return Ok(None);
+        };
+        let json = child
+            .read_scalar::<String>()
+            .ok_or_else(|| PyValueError::new_err("Categories must be a string"))?;
+        // JSON object keys are strings, the category values are integers
+        let labels = py
+            .import("json")?
+            .call_method1("loads", (json,))?
+            .extract::<HashMap<String, String>>()?;
+        let categories = PyDict::new(py);
+        for (value, label) in labels {
+            let value = value
+                .parse::<i64>()
+                .map_err(|_| PyValueError::new_err(format!("Invalid category value: {}", value)))?;
+            categories.set_item(value, label)?;
+        }
+        Ok(Some(categories))
+    }
+
+    /// Returns the number of chunks along each dimension.
+    fn chunk_grid<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        PyTuple::new(py, self.chunk_grid_internal()?)
+    }
+
+    /// Returns the total number of chunks of the array.
+    fn chunk_count(&self) -> PyResult<u64> {
+        Ok(self.chunk_grid_internal()?.iter().product())
+    }
+
+    /// Reads the values at the given points, which are index tuples with one index per dimension.
+    /// Points are grouped by the chunk containing them so every chunk is decoded only once.
+    /// Returns a 1-D array aligned with the order of the input points.
+    fn read_points<'py>(
+        &self,
+        py: Python<'py>,
+        points: Vec<Vec<i64>>,
+    ) -> PyResult<Bound<'py, PyUntypedArray>> {
+        let points = points
+            .iter()
+            .map(|point| normalize_point(point, &self.shape))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let values = match self.reader.data_type() {
+            DataType::Int8Array => {
+                read_points_typed::<i8>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::Uint8Array => {
+                read_points_typed::<u8>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::Int16Array => {
+                read_points_typed::<i16>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::Uint16Array => {
+                read_points_typed::<u16>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::Int32Array => {
+                read_points_typed::<i32>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::Uint32Array => {
+                read_points_typed::<u32>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::Int64Array => {
+                read_points_typed::<i64>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::Uint64Array => {
+                read_points_typed::<u64>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::FloatArray => {
+                read_points_typed::<f32>(&self.reader, &points, self.io_hints, py)
+            }
+            DataType::DoubleArray => {
+                read_points_typed::<f64>(&self.reader, &points, self.io_hints, py)
+            }
+            _ => Err(PyValueError::new_err(
+                "Point reads are only supported for numeric arrays",
+            )),
+        }?;
+        if self.is_float16() {
+            return Ok(values
+                .call_method1("astype", ("float16",))?
+                .downcast_into::<PyUntypedArray>()?);
+        }
+        Ok(values)
+    }
+
+    /// Reads several index expressions in one call and returns the arrays in the same order.
+    /// All indices are validated before the first decode, so an invalid index reads nothing.
+    fn read_ranges<'py>(
+        &self,
+        py: Python<'py>,
+        ranges: Vec<ArrayIndex>,
+    ) -> PyResult<Vec<Bound<'py, PyUntypedArray>>> {
+        let plans = ranges
+            .iter()
+            .map(|index| index.to_read_plan(&self.shape))
+            .collect::<PyResult<Vec<_>>>()?;
+        plans
+            .into_iter()
+            .map(|plan| self.read_array(py, plan))
+            .collect()
+    }
+
+    /// Computes a histogram of the values in the requested ranges without returning them to Python.
+    /// NaN values are ignored. Returns a tuple `(counts, bin_edges)` like `numpy.histogram`.
+    /// If `bins` is not given, integer arrays with at most `MAX_VALUE_BINS` distinct
+    /// possible values are counted per value, all other arrays use 10 bins.
+    #[pyo3(signature = (ranges, bins=None, range=None))]
+    fn histogram<'py>(
+        &self,
+        py: Python<'py>,
+        ranges: ArrayIndex,
+        bins: Option<usize>,
+        range: Option<(f64, f64)>,
+    ) -> PyResult<Bound<'py, PyTuple>> {
+        if bins == Some(0) {
+            return Err(PyValueError::new_err("bins must be positive"));
+        }
+        if let Some((low, high)) = range {
+            if low > high || !low.is_finite() || !high.is_finite() {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid histogram range ({}, {})",
+                    low, high
+                )));
+            }
+        }
+        let read_ranges = ranges.to_read_range(&self.shape)?;
+        let reader = &self.reader;
+        let (counts, edges) = match reader.data_type() {
+            DataType::Int8Array => {
+                histogram_typed::<i8>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::Uint8Array => {
+                histogram_typed::<u8>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::Int16Array => {
+                histogram_typed::<i16>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::Uint16Array => {
+                histogram_typed::<u16>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::Int32Array => {
+                histogram_typed::<i32>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::Uint32Array => {
+                histogram_typed::<u32>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::Int64Array => {
+                histogram_typed::<i64>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::Uint64Array => {
+                histogram_typed::<u64>(reader, read_ranges, bins, range, true, self.io_hints)
+            }
+            DataType::FloatArray => {
+                histogram_typed::<f32>(reader, read_ranges, bins, range, false, self.io_hints)
+            }
+            DataType::DoubleArray => {
+                histogram_typed::<f64>(reader, read_ranges, bins, range, false, self.io_hints)
+            }
+            _ => Err(PyValueError::new_err(
+                "Histograms are only supported for numeric arrays",
+            )),
+        }?;
+        PyTuple::new(
+            py,
+            [
+                counts.into_pyarray(py).into_any(),
+                edges.into_pyarray(py).into_any(),
+            ],
+        )
+    }
+
+    /// Computes the minimum, maximum and mean of the values in the requested ranges without
+    /// returning them to Python. NaN values are ignored. Returns a tuple `(min, max, mean)`,
+    /// which is all NaN if there are no other values.
+    fn reduce_minmax(&self, ranges: ArrayIndex) -> PyResult<(f64, f64, f64)> {
+        let read_ranges = ranges.to_read_range(&self.shape)?;
+        let reader = &self.reader;
+        match reader.data_type() {
+            DataType::FloatArray => reduce_typed::<f32>(reader, read_ranges, self.io_hints),
+            DataType::DoubleArray => reduce_typed::<f64>(reader, read_ranges, self.io_hints),
+            _ => Err(PyValueError::new_err(
+                "Reductions are only supported for float arrays",
+            )),
+        }
+    }
+
+    /// Value marking missing data, as passed to `write_array`, or None if none was stored.
+    #[getter]
+    fn fill_value(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        find_child(&self.reader, FILL_VALUE_KEY)
+            .map(|child| read_scalar_object(&child, py))
+            .transpose()
+    }
+
+    /// Compression of the array, e.g. `CompressionType.PFOR_DELTA_2D_INT16`.
+    #[getter]
+    fn compression(&self) -> PyResult<PyCompression> {
+        self.require_array()?;
+        Ok(PyCompression(PyCompressionType::from_omfilesrs(
+            &self.reader.compression(),
+        )))
+    }
+
+    #[getter]
+    fn scale_factor(&self) -> PyResult<f32> {
+        self.require_array()?;
+        Ok(self.reader.scale_factor())
+    }
+
+    #[getter]
+    fn add_offset(&self) -> PyResult<f32> {
+        self.require_array()?;
+        Ok(self.reader.add_offset())
+    }
+
+    #[getter]
+    fn chunk_dimensions(&self) -> PyResult<Vec<u64>> {
+        self.require_array()?;
+        Ok(self.reader.get_chunk_dimensions().to_vec())
+    }
+
+    /// Whether this variable is a group without data, which only holds children.
+    #[getter]
+    fn is_group(&self) -> bool {
+        matches!(self.reader.data_type(), DataType::None)
+    }
+
+    /// Readers of the direct children of this variable, in order. They share the backend
+    /// of this reader and only their metadata is read.
+    #[getter]
+    fn children(&self) -> Vec<Self> {
+        (0..self.reader.number_of_children())
+            .filter_map(|index| self.reader.get_child(index))
+            .map(|child| self.child(child))
+            .collect()
+    }
+
+    /// Scalar children of this variable, e.g. `units`, as a name to value dict.
+    #[getter]
+    fn attrs(&self, py: Python<'_>) -> PyResult<HashMap<String, PyObject>> {
+        scalar_attributes(&self.reader, py)
+    }
+
+    /// Returns an indented overview of this variable and all its descendants, one line per
+    /// variable with the dtype, shape, chunks and compression of arrays and the value of
+    /// scalars. Children are listed in index order.
+    fn describe(&self, py: Python<'_>) -> PyResult<String> {
+        let mut lines = Vec::new();
+        self.describe_tree(py, 0, &mut lines)?;
+        Ok(lines.join("\n"))
+    }
+
+    /// Returns the values of all scalars in the tree below and including this variable,
+    /// keyed by their slash separated path as in `variable_paths`.
+    fn read_all_scalars(&self, py: Python<'_>) -> PyResult<HashMap<String, PyObject>> {
+        let mut scalars = HashMap::new();
+        collect_scalars(&self.reader, "", py, &mut scalars)?;
+        Ok(scalars)
+    }
+
+    /// Returns the structure of this variable and its descendants as nested dicts without
+    /// reading any array data: `{name: {"dtype": ..., "shape": [...], "children": {...}}}`.
+    /// Groups have the dtype "group", string scalars "str".
+    fn to_tree<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let tree = PyDict::new(py);
+        tree.set_item(
+            self.reader.get_name().unwrap_or_default(),
+            self.tree_node(py)?,
+        )?;
+        Ok(tree)
+    }
+
+    /// Returns the sorted paths of all variables in the tree below and including this variable.
+    /// Only the names are collected during traversal, no other metadata is assembled.
+    fn variable_paths(&self) -> PyResult<Vec<String>> {
+        let mut paths = Vec::new();
+        collect_variable_paths(&self.reader, "", &mut paths);
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Returns the hit and miss counters and the size of the backend caches
+    /// as a dict with the keys `hits`, `misses`, `entries` and `bytes`.
+    /// All values are zero if caching is disabled.
+    fn cache_stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.backend.cache_stats().to_dict(py)
+    }
+
+    /// Sets the I/O coalescing of reads of this variable. Reads are split into requests of at
+    /// most `io_size_max` bytes and requests separated by less than `io_size_merge` bytes are
+    /// merged. `None` restores the defaults of 65536 and 512 bytes. Children opened
+    /// afterwards inherit the hints.
+    #[pyo3(signature = (io_size_max=None, io_size_merge=None))]
+    fn set_io_hints(
+        &mut self,
+        io_size_max: Option<u64>,
+        io_size_merge: Option<u64>,
+    ) -> PyResult<()> {
+        if io_size_max == Some(0) {
+            return Err(PyValueError::new_err("io_size_max must be positive"));
+        }
+        self.io_hints = IoHints {
+            io_size_max,
+            io_size_merge,
+        };
+        Ok(())
+    }
+
+    /// Whether the file was deleted or replaced since it was opened. Only memory mapped
+    /// files are checked, other sources return False. The reader stays usable either way,
+    /// as the mapping keeps the data of a deleted file alive.
+    fn was_deleted(&self) -> bool {
+        self.backend.was_deleted()
+    }
+
+    /// Returns a reader of the root variable of this file which keeps recently read byte
+    /// ranges in memory, up to `max_bytes`. Repeated reads of the same chunks, e.g. time
+    /// series of the same region, are then served without accessing the file again.
+    fn with_cache(&self, max_bytes: u64) -> PyResult<Self> {
+        let backend = CachedBackend::new(self.backend.clone(), max_bytes);
+        let mut reader = Self::from_backend(BackendImpl::Cached(backend), self.squeeze)?;
+        reader.io_hints = self.io_hints;
+        Ok(reader)
+    }
+
+    /// Drops any prefetch buffers held by the backend. The reader stays usable
+    /// for data reads. This is a no-op for memory mapped files.
+    fn release_prefetch(&self) {
+        self.backend.release_prefetch();
+    }
+
     fn init_from_offset_size(&self, offset: u64, size: u64) -> PyResult<Self> {
         let reader = self
             .reader
             .init_child_from_offset_size(OmOffsetSize::new(offset, size))
             .map_err(convert_omfilesrs_error)?;
 
-        let shape = reader.get_dimensions().to_vec();
-        Ok(Self { reader, shape })
+        Ok(self.child(reader))
+    }
+
+    /// Checks the file structure and the metadata of all variables for consistency.
+    /// Returns a list of problems found, which is empty for a healthy file.
+    /// The magic number and format version are already verified when the file is opened.
+    fn validate(&self) -> PyResult<Vec<String>> {
+        let file_size = self.backend.count() as u64;
+        let mut problems = Vec::new();
+
+        let mut variables = self
+            .reader
+            .get_flat_variable_metadata()
+            .into_iter()
+            .collect::<Vec<_>>();
+        variables.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (path, offset_size) in variables {
+            let end = offset_size.offset.checked_add(offset_size.size);
+            if end.is_none_or(|end| end > file_size) {
+                problems.push(format!(
+                    "{}: variable at offset {} with size {} exceeds file size {}",
+                    path, offset_size.offset, offset_size.size, file_size
+                ));
+                continue;
+            }
+            match self.reader.init_child_from_offset_size(offset_size) {
+                Ok(variable) => validate_array_metadata(&variable, &path, &mut problems),
+                Err(e) => problems.push(format!("{}: {}", path, e)),
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Returns the format version of the file and whether it uses the legacy header-only
+    /// layout or a trailer pointing to the root variable.
+    fn format_details<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let version = read_format_version(&self.backend)?;
+        let details = PyDict::new(py);
+        details.set_item("version", version)?;
+        details.set_item("layout", if version < 3 { "legacy" } else { "trailer" })?;
+        Ok(details)
+    }
+
+    /// Summarizes this variable in a JSON serializable dict. `kind` is one of
+    /// "array", "scalar" or "group". `dtype`, `size` (the number of elements) and
+    /// the compression keys are None for scalars and groups.
+    fn info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dtype = self.reader.data_type();
+        let kind = if is_array(&dtype) {
+            "array"
+        } else if matches!(dtype, DataType::None) {
+            "group"
+        } else {
+            "scalar"
+        };
+        let info = PyDict::new(py);
+        info.set_item("name", self.reader.get_name())?;
+        info.set_item("kind", kind)?;
+        info.set_item("shape", &self.shape)?;
+        info.set_item("num_children", self.reader.number_of_children())?;
+        info.set_item("record_size", self.record_size())?;
+        if kind == "array" {
+            info.set_item("dtype", self.numpy_dtype())?;
+            info.set_item("size", self.shape.iter().product::<u64>())?;
+            info.set_item("compression", compression_name(&self.reader.compression()))?;
+            info.set_item("scale_factor", self.reader.scale_factor())?;
+            info.set_item("add_offset", self.reader.add_offset())?;
+            info.set_item("chunk_dimensions", self.reader.get_chunk_dimensions())?;
+        } else {
+            for key in [
+                "dtype",
+                "size",
+                "compression",
+                "scale_factor",
+                "add_offset",
+                "chunk_dimensions",
+            ] {
+                info.set_item(key, py.None())?;
+            }
+        }
+        Ok(info)
+    }
+
+    /// Summarizes the variable in one line, e.g.
+    /// `OmFilePyReader(name='t2m', shape=[721, 1440], dtype=float32, compression='pfor_delta_2d', children=2)`.
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let name = self.reader.get_name().unwrap_or_default();
+        let dtype = self.reader.data_type();
+        let children = self.reader.number_of_children();
+        if is_array(&dtype) {
+            Ok(format!(
+                "OmFilePyReader(name='{}', shape={:?}, dtype={}, compression='{}', children={})",
+                name,
+                self.shape,
+                self.numpy_dtype(),
+                compression_name(&self.reader.compression()),
+                children
+            ))
+        } else if matches!(dtype, DataType::None) {
+            Ok(format!(
+                "OmFilePyReader(name='{}', group, children={})",
+                name, children
+            ))
+        } else {
+            let value = read_scalar_object(&self.reader, py)?;
+            Ok(format!(
+                "OmFilePyReader(name='{}', value={}, children={})",
+                name,
+                value.bind(py).repr()?,
+                children
+            ))
+        }
+    }
+
+    /// Summarizes the variable over multiple lines, adapted to arrays, scalars and groups.
+    fn __str__(&self, py: Python<'_>) -> PyResult<String> {
+        let name = self.reader.get_name().unwrap_or_default();
+        let dtype = self.reader.data_type();
+        let children = self.reader.number_of_children();
+        if is_array(&dtype) {
+            Ok(format!(
+                "OmFilePyReader '{}' (array)\n  dtype: {}\n  shape: {:?}\n  chunks: {:?}\n  compression: {} (scale_factor={}, add_offset={})\n  children: {}",
+                name,
+                self.numpy_dtype(),
+                self.shape,
+                self.reader.get_chunk_dimensions(),
+                compression_name(&self.reader.compression()),
+                self.reader.scale_factor(),
+                self.reader.add_offset(),
+                children
+            ))
+        } else if matches!(dtype, DataType::None) {
+            Ok(format!(
+                "OmFilePyReader '{}' (group)\n  children: {}",
+                name, children
+            ))
+        } else {
+            let value = read_scalar_object(&self.reader, py)?;
+            Ok(format!(
+                "OmFilePyReader '{}' (scalar)\n  value: {}\n  children: {}",
+                name,
+                value.bind(py).repr()?,
+                children
+            ))
+        }
+    }
+
+    /// Returns the name and dtype name of each direct child without visiting grandchildren.
+    /// Groups have the dtype name "group" and strings "str".
+    fn list_children(&self) -> Vec<(String, &'static str)> {
+        (0..self.reader.number_of_children())
+            .filter_map(|index| self.reader.get_child(index))
+            .map(|child| {
+                (
+                    child.get_name().unwrap_or_default(),
+                    dtype_name(&child.data_type()),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the first direct child with the given name, or None if there is none.
+    /// Unlike `reader[name]`, a missing child does not raise.
+    fn get_child_by_name(&self, name: &str) -> Option<Self> {
+        find_child(&self.reader, name).map(|child| self.child(child))
+    }
+
+    /// Opens a descendant by its slash separated path relative to this variable,
+    /// e.g. `"group/temperature"`. Only the variables along the path are visited.
+    fn open_path(&self, path: &str) -> PyResult<Self> {
+        find_variable_by_path(&self.reader, path)
+            .map(|variable| self.child(variable))
+            .ok_or_else(|| PyKeyError::new_err(path.to_string()))
+    }
+
+    /// Returns the value of a scalar variable as a Python int, float or str.
+    /// With `name`, returns the value of the first direct child with that name instead.
+    #[pyo3(signature = (name=None))]
+    fn get_scalar(&self, py: Python<'_>, name: Option<&str>) -> PyResult<PyObject> {
+        let Some(name) = name else {
+            return read_scalar_object(&self.reader, py);
+        };
+        let child =
+            find_child(&self.reader, name).ok_or_else(|| PyKeyError::new_err(name.to_string()))?;
+        let dtype = child.data_type();
+        if is_array(&dtype) || matches!(dtype, DataType::None) {
+            return Err(PyTypeError::new_err(format!(
+                "Child {name} is not a scalar"
+            )));
+        }
+        read_scalar_object(&child, py)
+    }
+
+    /// Returns an array-like proxy which only reads the data that is indexed.
+    fn as_lazy(slf: Py<Self>) -> OmLazyArray {
+        OmLazyArray::new(slf)
     }
 
     fn dtype(&self) -> PyResult<String> {
-        Ok(to_numpy_dtype(&self.reader.data_type()).to_string())
+        self.require_array()?;
+        Ok(self.numpy_dtype())
     }
 
-    fn variable_name(&self) -> PyResult<String> {
+    pub(crate) fn variable_name(&self) -> PyResult<String> {
         Ok(self.reader.get_name().unwrap_or("".to_string()))
     }
 
+    /// Returns the provenance metadata stored as scalar children of this variable.
+    /// Only the keys listed in `FILE_METADATA_KEYS` are recognized.
+    fn file_metadata(&self, py: Python<'_>) -> PyResult<HashMap<String, PyObject>> {
+        let mut metadata = HashMap::new();
+        for index in 0..self.reader.number_of_children() {
+            let Some(child) = self.reader.get_child(index) else {
+                continue;
+            };
+            let Some(name) = child.get_name() else {
+                continue;
+            };
+            if FILE_METADATA_KEYS.contains(&name.as_str()) {
+                metadata.insert(name, read_scalar_object(&child, py)?);
+            }
+        }
+        Ok(metadata)
+    }
+
+    /// Describes how the data of this variable is compressed.
+    /// All chunks of a variable currently share one compression, so `uniform` is always true
+    /// and `chunks` is None. Both keys are reserved for reporting per-chunk compression.
+    fn compression_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.require_array()?;
+        let info = PyDict::new(py);
+        info.set_item("compression", compression_name(&self.reader.compression()))?;
+        info.set_item("scale_factor", self.reader.scale_factor())?;
+        info.set_item("add_offset", self.reader.add_offset())?;
+        info.set_item("uniform", true)?;
+        info.set_item("chunks", py.None())?;
+        Ok(info)
+    }
+
+    #[pyo3(
+        text_signature = "(ranges, /, *, saturation_policy='keep', view_dtype=None, with_indices=False, decode_categories=False)",
+        signature = (ranges, saturation_policy=None, view_dtype=None, with_indices=false, decode_categories=false)
+    )]
+    fn read<'py>(
+        &self,
+        py: Python<'py>,
+        ranges: ArrayIndex,
+        saturation_policy: Option<&str>,
+        view_dtype: Option<&str>,
+        with_indices: bool,
+        decode_categories: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let saturation_policy = saturation_policy
+            .map(SaturationPolicy::from_str)
+            .transpose()?
+            .unwrap_or(SaturationPolicy::Keep);
+
+        let plan = ranges.to_read_plan(&self.shape)?;
+        let mut array = self.read_array(py, plan.clone())?;
+        if saturation_policy == SaturationPolicy::Nan {
+            self.mask_saturated_values(&array)?;
+        }
+        if let Some(view_dtype) = view_dtype {
+            array = view_as(array, view_dtype)?;
+        }
+        let data = if decode_categories {
+            let categories = self
+                .categories(py)?
+                .ok_or_else(|| PyValueError::new_err("Variable has no categories"))?;
+            // Values without a category are decoded to None
+            let lookup = py
+                .import("numpy")?
+                .call_method1("frompyfunc", (categories.getattr("get")?, 1, 1))?;
+            lookup.call1((array,))?
+        } else {
+            array.into_any()
+        };
+
+        if !with_indices {
+            return Ok(data);
+        }
+        // Axes removed by the squeeze do not get an index array
+        let indices = plan
+            .ranges
+            .iter()
+            .zip(&plan.steps)
+            .zip(&plan.integer_axes)
+            .filter(|(_, &is_integer)| !self.squeeze || !is_integer)
+            .map(|((range, &step), _)| {
+                (range.start as i64..range.end as i64)
+                    .step_by(step as usize)
+                    .collect::<Vec<_>>()
+                    .into_pyarray(py)
+            })
+            .collect::<Vec<_>>();
+        (data, indices).into_bound_py_any(py)
+    }
+
+    /// Reads the requested ranges into the preallocated array `out`.
+    /// If the dtype of `out` differs from the stored dtype, values are cast while copying.
+    /// Casts that may lose information emit a `RuntimeWarning`.
+    /// NaN values cannot be represented in integer outputs and are replaced by `nan_replacement`.
+    /// With `into_offset`, the data is written to the region of `out` starting at that position.
+    #[pyo3(signature = (out, ranges, nan_replacement=None, into_offset=None))]
+    fn read_into(
+        &self,
+        py: Python<'_>,
+        out: &Bound<'_, PyUntypedArray>,
+        ranges: ArrayIndex,
+        nan_replacement: Option<i64>,
+        into_offset: Option<Vec<usize>>,
+    ) -> PyResult<()> {
+        if !matches!(out.dtype().kind(), b'i' | b'u' | b'f') {
+            return Err(PyTypeError::new_err(format!(
+                "Cannot read into an array of dtype {}, expected an integer or float dtype",
+                out.dtype()
+            )));
+        }
+        if !out.is_c_contiguous() {
+            return Err(PyValueError::new_err("Output array must be C-contiguous"));
+        }
+        if !out
+            .getattr("flags")?
+            .getattr("writeable")?
+            .extract::<bool>()?
+        {
+            return Err(PyValueError::new_err("Output array is read-only"));
+        }
+
+        let plan = ranges.to_read_plan(&self.shape)?;
+        if into_offset.is_none() && self.try_decode_into(out, &plan)? {
+            return Ok(());
+        }
+        let data = self.read_array(py, plan)?;
+        let target = match into_offset {
+            None => {
+                if data.shape() != out.shape() {
+                    return Err(PyValueError::new_err(format!(
+                        "Output array has shape {:?}, but the requested data has shape {:?}",
+                        out.shape(),
+                        data.shape()
+                    )));
+                }
+                out.as_any().clone()
+            }
+            Some(offset) => {
+                if offset.len() != out.ndim() || data.ndim() != out.ndim() {
+                    return Err(PyIndexError::new_err(format!(
+                        "into_offset {:?} and requested data with shape {:?} do not match the {} dimensions of the output array",
+                        offset,
+                        data.shape(),
+                        out.ndim()
+                    )));
+                }
+                let in_bounds = offset.iter().zip(data.shape()).zip(out.shape()).all(
+                    |((&start, &len), &size)| start.checked_add(len).is_some_and(|end| end <= size),
+                );
+                if !in_bounds {
+                    return Err(PyIndexError::new_err(format!(
+                        "Requested data with shape {:?} at offset {:?} exceeds the output array with shape {:?}",
+                        data.shape(),
+                        offset,
+                        out.shape()
+                    )));
+                }
+                // Basic slicing returns a view, so copying into it writes to `out`
+                let region = PyTuple::new(
+                    py,
+                    offset.iter().zip(data.shape()).map(|(&start, &len)| {
+                        PySlice::new(py, start as isize, (start + len) as isize, 1)
+                    }),
+                )?;
+                out.get_item(region)?
+            }
+        };
+
+        let numpy = py.import("numpy")?;
+        let data_dtype = data.dtype();
+        let mut data = data.into_any();
+        if data_dtype.kind() == b'f' && matches!(out.dtype().kind(), b'i' | b'u') {
+            let nan_mask = numpy.call_method1("isnan", (&data,))?;
+            if nan_mask.call_method0("any")?.extract::<bool>()? {
+                let Some(nan_replacement) = nan_replacement else {
+                    return Err(PyValueError::new_err(format!(
+                        "Data contains NaN values which cannot be represented as {}. Pass nan_replacement to fill them.",
+                        out.dtype()
+                    )));
+                };
+                data = numpy.call_method1("where", (nan_mask, nan_replacement, &data))?;
+            }
+        }
+
+        let is_safe_cast = numpy
+            .call_method1("can_cast", (&data_dtype, out.dtype(), "safe"))?
+            .extract::<bool>()?;
+        if !is_safe_cast {
+            let message = CString::new(format!(
+                "Casting from {} to {} may lose precision",
+                data_dtype,
+                out.dtype()
+            ))?;
+            PyErr::warn(py, py.get_type::<PyRuntimeWarning>().as_any(), &message, 1)?;
+        }
+
+        let kwargs = [("casting", "unsafe")].into_py_dict(py)?;
+        numpy.call_method("copyto", (target, data), Some(&kwargs))?;
+        Ok(())
+    }
+
+    /// Reads the requested ranges into the memory of a preallocated `torch.Tensor`.
+    /// The tensor must be contiguous, on the CPU and of the stored dtype.
+    fn read_into_torch(
+        &self,
+        py: Python<'_>,
+        tensor: &Bound<'_, PyAny>,
+        ranges: ArrayIndex,
+    ) -> PyResult<()> {
+        let device = tensor
+            .getattr("device")?
+            .getattr("type")?
+            .extract::<String>()?;
+        if device != "cpu" {
+            return Err(PyValueError::new_err(format!(
+                "Tensor must be on the CPU, but is on {}",
+                device
+            )));
+        }
+        if !tensor.call_method0("is_contiguous")?.extract::<bool>()? {
+            return Err(PyValueError::new_err("Tensor must be contiguous"));
+        }
+        let expected_dtype = format!("torch.{}", self.numpy_dtype());
+        let tensor_dtype = tensor.getattr("dtype")?.str()?.to_string();
+        if tensor_dtype != expected_dtype {
+            return Err(PyTypeError::new_err(format!(
+                "Tensor has dtype {}, but the variable is stored as {}",
+                tensor_dtype, expected_dtype
+            )));
+        }
+
+        // The numpy view shares the memory of the tensor
+        let out = tensor
+            .call_method0("detach")?
+            .call_method0("numpy")?
+            .downcast_into::<PyUntypedArray>()?;
+        self.read_into(py, &out, ranges, None, None)
+    }
+
+    /// Reads the given ranges together with the scalar children of this variable,
+    /// e.g. `units` or `long_name`. Returns a named tuple `(array, attrs)`.
+    fn read_with_attrs<'py>(
+        &self,
+        py: Python<'py>,
+        ranges: ArrayIndex,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let plan = ranges.to_read_plan(&self.shape)?;
+        let array = self.read_array(py, plan)?;
+        let attrs = scalar_attributes(&self.reader, py)?;
+        array_with_attrs_type(py)?.call1((array, attrs))
+    }
+
+    /// Reads the given ranges as a `numpy.ma.MaskedArray`. NaN values of float arrays are
+    /// masked, e.g. positions that were never written. Values equal to `fill_value` are
+    /// masked as well, which is the only way to mask values of integer arrays.
+    /// `fill_value` defaults to the fill value stored with the array.
+    #[pyo3(signature = (ranges, fill_value=None))]
+    fn read_masked<'py>(
+        &self,
+        py: Python<'py>,
+        ranges: ArrayIndex,
+        fill_value: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let fill_value = match fill_value {
+            Some(fill_value) => Some(fill_value),
+            None => self.fill_value(py)?.map(|value| value.into_bound(py)),
+        };
+        let plan = ranges.to_read_plan(&self.shape)?;
+        let array = self.read_array(py, plan)?;
+        let numpy = py.import("numpy")?;
+        let mut mask = if self.numpy_dtype().starts_with("float") {
+            numpy.call_method1("isnan", (&array,))?
+        } else {
+            numpy.call_method1("zeros", (array.shape().to_vec(), "bool"))?
+        };
+        if let Some(fill_value) = fill_value {
+            let is_fill = numpy.call_method1("equal", (&array, fill_value))?;
+            mask = numpy.call_method1("logical_or", (mask, is_fill))?;
+        }
+        numpy
+            .getattr("ma")?
+            .call_method1("masked_array", (array, mask))
+    }
+
+    /// Number of direct children of this variable.
+    fn __len__(&self) -> usize {
+        self.reader.number_of_children() as usize
+    }
+
+    /// Iterates over readers of the direct children of this variable, in order.
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, self.children())?.try_iter()
+    }
+
+    /// Reads data with numpy basic indexing, or returns the direct child with the given name
+    /// if the key is a string.
     fn __getitem__<'py>(
+        &self,
+        py: Python<'py>,
+        key: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if let Ok(name) = key.downcast::<PyString>() {
+            return self.child_by_name(name.to_str()?)?.into_bound_py_any(py);
+        }
+        Ok(self.read_index(py, key.extract()?)?.into_any())
+    }
+}
+
+impl OmFilePyReader {
+    pub(crate) fn read_index<'py>(
         &self,
         py: Python<'py>,
         ranges: ArrayIndex,
     ) -> PyResult<Bound<'py, PyUntypedArray>> {
-        let read_ranges = ranges.to_read_range(&self.shape)?;
+        let plan = ranges.to_read_plan(&self.shape)?;
+        self.read_array(py, plan)
+    }
+
+    fn child_by_name(&self, name: &str) -> PyResult<Self> {
+        let dtype = self.reader.data_type();
+        if !is_array(&dtype) && !matches!(dtype, DataType::None) {
+            return Err(PyValueError::new_err(format!(
+                "Cannot look up children by name on scalar variable {}",
+                self.reader.get_name().unwrap_or_default()
+            )));
+        }
+        find_child(&self.reader, name)
+            .map(|child| self.child(child))
+            .ok_or_else(|| PyKeyError::new_err(name.to_string()))
+    }
 
+    /// Executes a read plan and converts the result to a numpy array of the stored dtype.
+    fn read_array<'py>(
+        &self,
+        py: Python<'py>,
+        plan: ReadPlan,
+    ) -> PyResult<Bound<'py, PyUntypedArray>> {
         let reader = &self.reader;
         let dtype = reader.data_type();
 
+        // Empty selections are answered without touching the decoder
+        if is_array(&dtype) && plan.ranges.iter().any(|range| range.start == range.end) {
+            let shape = plan.output_shape(self.squeeze);
+            let empty = py
+                .import("numpy")?
+                .call_method1("empty", (shape, self.numpy_dtype()))?;
+            return Ok(empty.downcast_into::<PyUntypedArray>()?);
+        }
+
         let scalar_error =
             PyErr::new::<pyo3::exceptions::PyValueError, _>("Scalar data types are not supported");
 
@@ -134,63 +1248,896 @@ impl OmFilePyReader {
             omfiles_rs::core::data_types::DataType::Double => Err(scalar_error),
             omfiles_rs::core::data_types::DataType::String => Err(scalar_error),
             omfiles_rs::core::data_types::DataType::Int8Array => {
-                read_untyped_array::<i8>(&reader, read_ranges, py)
+                read_untyped_array::<i8>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::Uint8Array => {
-                read_untyped_array::<u8>(&reader, read_ranges, py)
+                read_untyped_array::<u8>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::Int16Array => {
-                read_untyped_array::<i16>(&reader, read_ranges, py)
+                read_untyped_array::<i16>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::Uint16Array => {
-                read_untyped_array::<u16>(&reader, read_ranges, py)
+                read_untyped_array::<u16>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::Int32Array => {
-                read_untyped_array::<i32>(&reader, read_ranges, py)
+                read_untyped_array::<i32>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::Uint32Array => {
-                read_untyped_array::<u32>(&reader, read_ranges, py)
+                read_untyped_array::<u32>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::Int64Array => {
-                read_untyped_array::<i64>(&reader, read_ranges, py)
+                read_untyped_array::<i64>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::Uint64Array => {
-                read_untyped_array::<u64>(&reader, read_ranges, py)
+                read_untyped_array::<u64>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::FloatArray => {
-                read_untyped_array::<f32>(&reader, read_ranges, py)
+                read_untyped_array::<f32>(&reader, plan, self.squeeze, self.io_hints, py)
             }
             omfiles_rs::core::data_types::DataType::DoubleArray => {
-                read_untyped_array::<f64>(&reader, read_ranges, py)
-            }
-            omfiles_rs::core::data_types::DataType::StringArray => {
-                unimplemented!("String arrays are currently not implemented")
+                read_untyped_array::<f64>(&reader, plan, self.squeeze, self.io_hints, py)
             }
+            // omfiles-rs cannot encode or decode string arrays yet
+            omfiles_rs::core::data_types::DataType::StringArray => Err(
+                PyNotImplementedError::new_err("String arrays are currently not supported"),
+            ),
         };
 
         let untyped_py_array = untyped_py_array_or_error?;
+        if self.is_float16() {
+            return Ok(untyped_py_array
+                .call_method1("astype", ("float16",))?
+                .downcast_into::<PyUntypedArray>()?);
+        }
 
         return Ok(untyped_py_array);
     }
+
+    /// Size in bytes of the metadata record of this variable, not of its compressed data.
+    /// None if the reader was not opened from a known record.
+    fn record_size(&self) -> Option<u64> {
+        self.reader
+            .offset_size
+            .as_ref()
+            .map(|offset_size| offset_size.size)
+    }
+
+    /// Whether this is a float16 array, which is stored as float32.
+    fn is_float16(&self) -> bool {
+        matches!(self.reader.data_type(), DataType::FloatArray)
+            && find_child(&self.reader, STORED_DTYPE_KEY)
+                .and_then(|child| child.read_scalar::<String>())
+                .is_some_and(|dtype| dtype == "float16")
+    }
+
+    /// The numpy dtype of read results, which differs from the stored type for float16 arrays.
+    fn numpy_dtype(&self) -> String {
+        if self.is_float16() {
+            return "float16".to_string();
+        }
+        to_numpy_dtype(&self.reader.data_type()).to_string()
+    }
+
+    fn from_backend(backend: BackendImpl, squeeze: bool) -> PyResult<Self> {
+        let backend = Arc::new(backend);
+        let version = read_format_version(&backend)?;
+        if version > OM_MAX_VERSION {
+            return Err(OmUnsupportedVersionError::new_err(format!(
+                "Unsupported om file version {}. The highest supported version is {}",
+                version, OM_MAX_VERSION
+            )));
+        }
+        // With a valid magic number any remaining failure means missing data
+        let reader = OmFileReader::new(backend.clone())
+            .map_err(|e| OmTruncatedFileError::new_err(format!("Truncated om file: {}", e)))?;
+        let shape = reader.get_dimensions().to_vec();
+
+        Ok(Self {
+            reader,
+            backend,
+            squeeze,
+            io_hints: IoHints::default(),
+            shape,
+        })
+    }
+
+    /// Decodes directly into the memory of `out` if it has the stored dtype and exactly the
+    /// shape of the requested data. Returns false if the data has to be copied instead.
+    fn try_decode_into(&self, out: &Bound<'_, PyUntypedArray>, plan: &ReadPlan) -> PyResult<bool> {
+        let output_shape = plan.output_shape(self.squeeze);
+        let is_direct = !plan.is_strided()
+            && !output_shape.contains(&0)
+            && output_shape
+                .iter()
+                .map(|&len| len as usize)
+                .eq(out.shape().iter().copied());
+        if !is_direct {
+            return Ok(false);
+        }
+        match self.reader.data_type() {
+            DataType::Int8Array => decode_into_typed::<i8>(&self.reader, out, plan, self.io_hints),
+            DataType::Uint8Array => decode_into_typed::<u8>(&self.reader, out, plan, self.io_hints),
+            DataType::Int16Array => {
+                decode_into_typed::<i16>(&self.reader, out, plan, self.io_hints)
+            }
+            DataType::Uint16Array => {
+                decode_into_typed::<u16>(&self.reader, out, plan, self.io_hints)
+            }
+            DataType::Int32Array => {
+                decode_into_typed::<i32>(&self.reader, out, plan, self.io_hints)
+            }
+            DataType::Uint32Array => {
+                decode_into_typed::<u32>(&self.reader, out, plan, self.io_hints)
+            }
+            DataType::Int64Array => {
+                decode_into_typed::<i64>(&self.reader, out, plan, self.io_hints)
+            }
+            DataType::Uint64Array => {
+                decode_into_typed::<u64>(&self.reader, out, plan, self.io_hints)
+            }
+            DataType::FloatArray => {
+                decode_into_typed::<f32>(&self.reader, out, plan, self.io_hints)
+            }
+            DataType::DoubleArray => {
+                decode_into_typed::<f64>(&self.reader, out, plan, self.io_hints)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Reads the complete array. No axes are removed, as there are no integer indices.
+    pub(crate) fn read_all<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyUntypedArray>> {
+        self.require_array()?;
+        let ranges = self.shape.iter().map(|&dim| 0..dim).collect();
+        self.read_array(py, ReadPlan::contiguous(ranges))
+    }
+
+    /// The compression, the scale factor and the offset of an array.
+    pub(crate) fn compression_parameters(&self) -> PyResult<(PyCompressionType, f32, f32)> {
+        self.require_array()?;
+        Ok((
+            PyCompressionType::from_omfilesrs(&self.reader.compression()),
+            self.reader.scale_factor(),
+            self.reader.add_offset(),
+        ))
+    }
+
+    fn tree_node<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dtype = self.reader.data_type();
+        let node = PyDict::new(py);
+        if is_array(&dtype) {
+            node.set_item("dtype", self.numpy_dtype())?;
+        } else {
+            node.set_item("dtype", dtype_name(&dtype))?;
+        }
+        node.set_item("shape", &self.shape)?;
+        let children = PyDict::new(py);
+        for child in self.children() {
+            children.set_item(
+                child.reader.get_name().unwrap_or_default(),
+                child.tree_node(py)?,
+            )?;
+        }
+        node.set_item("children", children)?;
+        Ok(node)
+    }
+
+    fn describe_tree(&self, py: Python<'_>, depth: usize, lines: &mut Vec<String>) -> PyResult<()> {
+        let mut name = self.reader.get_name().unwrap_or_default();
+        if depth == 0 && name.is_empty() {
+            name = "/".to_string();
+        }
+        let dtype = self.reader.data_type();
+        let details = if is_array(&dtype) {
+            format!(
+                "{}, shape={:?}, chunks={:?}, {}",
+                self.numpy_dtype(),
+                self.shape,
+                self.reader.get_chunk_dimensions(),
+                compression_name(&self.reader.compression())
+            )
+        } else if matches!(dtype, DataType::None) {
+            "group".to_string()
+        } else {
+            let value = read_scalar_object(&self.reader, py)?;
+            format!("scalar, {}", value.bind(py).repr()?)
+        };
+        lines.push(format!("{}{} ({})", "  ".repeat(depth), name, details));
+        for child in self.children() {
+            child.describe_tree(py, depth + 1, lines)?;
+        }
+        Ok(())
+    }
+
+    /// Wraps the reader of another variable in the same file.
+    fn child(&self, reader: OmFileReader<BackendImpl>) -> Self {
+        let shape = reader.get_dimensions().to_vec();
+
+        Self {
+            reader,
+            backend: self.backend.clone(),
+            squeeze: self.squeeze,
+            io_hints: self.io_hints,
+            shape,
+        }
+    }
+
+    /// Decodes the given ranges of a variable of the file at `file_path` without squeezing.
+    pub fn read_variable<'py>(
+        py: Python<'py>,
+        file_path: &str,
+        variable: OmOffsetSize,
+        read_ranges: Vec<Range<u64>>,
+    ) -> PyResult<Bound<'py, PyUntypedArray>> {
+        let root = Self::from_path(file_path, false, false, 0, None)?;
+        root.read_child_variable(py, variable, read_ranges)
+    }
+
+    /// Like `read_variable`, but for a file held in memory.
+    pub fn read_variable_from_bytes<'py>(
+        py: Python<'py>,
+        data: Vec<u8>,
+        variable: OmOffsetSize,
+        read_ranges: Vec<Range<u64>>,
+    ) -> PyResult<Bound<'py, PyUntypedArray>> {
+        let root = Self::from_backend(BackendImpl::InMemory(InMemoryBackend::new(data)), false)?;
+        root.read_child_variable(py, variable, read_ranges)
+    }
+
+    fn read_child_variable<'py>(
+        &self,
+        py: Python<'py>,
+        variable: OmOffsetSize,
+        read_ranges: Vec<Range<u64>>,
+    ) -> PyResult<Bound<'py, PyUntypedArray>> {
+        let reader = self
+            .reader
+            .init_child_from_offset_size(variable)
+            .map_err(convert_omfilesrs_error)?;
+        self.child(reader)
+            .read_array(py, ReadPlan::contiguous(read_ranges))
+    }
+
+    fn require_array(&self) -> PyResult<()> {
+        if !is_array(&self.reader.data_type()) {
+            return Err(PyValueError::new_err("Variable is not an array"));
+        }
+        Ok(())
+    }
+
+    fn chunk_grid_internal(&self) -> PyResult<Vec<u64>> {
+        self.require_array()?;
+        Ok(self
+            .reader
+            .get_dimensions()
+            .iter()
+            .zip(self.reader.get_chunk_dimensions())
+            .map(|(dim_size, chunk_size)| dim_size.div_ceil(*chunk_size))
+            .collect())
+    }
+
+    /// Replaces values that were clamped to the limits of the int16 quantization
+    /// during compression with NaN. Only applies to arrays stored with int16 compression.
+    ///
+    /// omfiles-rs decodes straight to floats without exposing the quantized values, so
+    /// saturation is detected on the decoded values: a value is saturated if it equals
+    /// the dequantized limit in 32 bit precision, which is how the codec dequantizes.
+    /// float64 arrays are rounded to 32 bit for the comparison. Other dtypes, e.g.
+    /// float16 where the rounding hides the limit, are rejected.
+    fn mask_saturated_values(&self, array: &Bound<'_, PyUntypedArray>) -> PyResult<()> {
+        let compression = self.reader.compression();
+        match compression {
+            CompressionType::PforDelta2dInt16 | CompressionType::PforDelta2dInt16Logarithmic => {}
+            _ => return Ok(()),
+        }
+        let scale_factor = self.reader.scale_factor();
+        let add_offset = self.reader.add_offset();
+        let dequantize = |quantized: i16| -> f32 {
+            match compression {
+                CompressionType::PforDelta2dInt16Logarithmic => {
+                    10f32.powf(quantized as f32 / scale_factor) - 1.0
+                }
+                _ => quantized as f32 / scale_factor + add_offset,
+            }
+        };
+        let lower = dequantize(SATURATED_INT16_MIN);
+        let upper = dequantize(SATURATED_INT16_MAX);
+        let is_saturated = |value: f32| value == lower || value == upper;
+
+        if let Ok(array) = array.downcast::<PyArrayDyn<f32>>() {
+            array.readwrite().as_array_mut().mapv_inplace(|value| {
+                if is_saturated(value) {
+                    f32::NAN
+                } else {
+                    value
+                }
+            });
+        } else if let Ok(array) = array.downcast::<PyArrayDyn<f64>>() {
+            array.readwrite().as_array_mut().mapv_inplace(|value| {
+                if is_saturated(value as f32) {
+                    f64::NAN
+                } else {
+                    value
+                }
+            });
+        } else {
+            return Err(PyValueError::new_err(format!(
+                "saturation_policy='nan' is not supported for {} arrays",
+                array.dtype()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Lowest quantized value int16 compression clamps to.
+const SATURATED_INT16_MIN: i16 = i16::MIN;
+/// Highest quantized value int16 compression clamps to. `i16::MAX` is reserved for NaN.
+const SATURATED_INT16_MAX: i16 = i16::MAX - 1;
+
+/// How values that were saturated during int16 quantization are returned on read.
+#[derive(Clone, Copy, PartialEq)]
+enum SaturationPolicy {
+    /// Return the clamped values as they were stored
+    Keep,
+    /// Replace the clamped values with NaN
+    Nan,
+}
+
+impl SaturationPolicy {
+    fn from_str(s: &str) -> PyResult<Self> {
+        match s {
+            "keep" => Ok(SaturationPolicy::Keep),
+            "nan" => Ok(SaturationPolicy::Nan),
+            _ => Err(PyValueError::new_err(format!(
+                "Unsupported saturation policy: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Validates a point against the array shape and resolves negative indices.
+fn normalize_point(point: &[i64], shape: &[u64]) -> PyResult<Vec<u64>> {
+    if point.len() != shape.len() {
+        return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(format!(
+            "Point {:?} has {} indices, but the array has {} dimensions",
+            point,
+            point.len(),
+            shape.len()
+        )));
+    }
+    point
+        .iter()
+        .zip(shape)
+        .map(|(&index, &dim_size)| {
+            let normalized = if index < 0 {
+                index + dim_size as i64
+            } else {
+                index
+            };
+            if normalized < 0 || normalized >= dim_size as i64 {
+                return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(format!(
+                    "Index {} is out of bounds for axis with size {}",
+                    index, dim_size
+                )));
+            }
+            Ok(normalized as u64)
+        })
+        .collect()
+}
+
+/// Decodes the plan into `out` without an intermediate array. Returns false if `out`
+/// does not have the dtype `T`. `out` must be C-contiguous with the output shape of the plan.
+fn decode_into_typed<T: Element + OmFileArrayDataType>(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    out: &Bound<'_, PyUntypedArray>,
+    plan: &ReadPlan,
+    io_hints: IoHints,
+) -> PyResult<bool> {
+    let Ok(out) = out.downcast::<PyArrayDyn<T>>() else {
+        return Ok(false);
+    };
+    let mut out = out.try_readwrite()?;
+    // Squeezed axes have length 1, so the memory layout matches the unsqueezed shape
+    let dimensions = plan.shape();
+    let shape = dimensions
+        .iter()
+        .map(|&len| len as usize)
+        .collect::<Vec<_>>();
+    let mut view = out
+        .as_array_mut()
+        .into_shape_with_order(IxDyn(&shape))
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    reader
+        .read_into::<T>(
+            &mut view,
+            &plan.ranges,
+            &vec![0; dimensions.len()],
+            &dimensions,
+            io_hints.io_size_max,
+            io_hints.io_size_merge,
+        )
+        .map_err(convert_omfilesrs_error)?;
+    Ok(true)
+}
+
+fn read_points_typed<'py, T: Element + OmFileArrayDataType + Clone + Zero>(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    points: &[Vec<u64>],
+    io_hints: IoHints,
+    py: Python<'py>,
+) -> PyResult<Bound<'py, PyUntypedArray>> {
+    let dimensions = reader.get_dimensions();
+    let chunks = reader.get_chunk_dimensions();
+
+    // Group the positions of the points by the coordinates of their containing chunk
+    let mut points_per_chunk: HashMap<Vec<u64>, Vec<usize>> = HashMap::new();
+    for (position, point) in points.iter().enumerate() {
+        let chunk = point
+            .iter()
+            .zip(chunks)
+            .map(|(index, chunk_size)| index / chunk_size)
+            .collect();
+        points_per_chunk.entry(chunk).or_default().push(position);
+    }
+
+    let mut values = vec![T::zero(); points.len()];
+    for (chunk, positions) in points_per_chunk {
+        let read_ranges = chunk
+            .iter()
+            .zip(chunks)
+            .zip(dimensions)
+            .map(|((chunk_index, chunk_size), dim_size)| {
+                chunk_index * chunk_size..((chunk_index + 1) * chunk_size).min(*dim_size)
+            })
+            .collect::<Vec<_>>();
+        let data = reader
+            .read::<T>(&read_ranges, io_hints.io_size_max, io_hints.io_size_merge)
+            .map_err(convert_omfilesrs_error)?;
+
+        for position in positions {
+            let local_index = points[position]
+                .iter()
+                .zip(&read_ranges)
+                .map(|(index, range)| (index - range.start) as usize)
+                .collect::<Vec<_>>();
+            values[position] = data[local_index.as_slice()].clone();
+        }
+    }
+
+    Ok(values.into_pyarray(py).as_untyped().to_owned())
+}
+
+/// Reinterprets the bytes of `array` as `view_dtype` without converting values.
+/// Both dtypes need to have the same itemsize.
+fn view_as<'py>(
+    array: Bound<'py, PyUntypedArray>,
+    view_dtype: &str,
+) -> PyResult<Bound<'py, PyUntypedArray>> {
+    let view_dtype = PyArrayDescr::new(array.py(), view_dtype)?;
+    if view_dtype.itemsize() != array.dtype().itemsize() {
+        return Err(PyValueError::new_err(format!(
+            "Cannot view {} as {}: itemsize {} does not match itemsize {}",
+            array.dtype(),
+            view_dtype,
+            array.dtype().itemsize(),
+            view_dtype.itemsize()
+        )));
+    }
+    Ok(array
+        .call_method1("view", (view_dtype,))?
+        .downcast_into::<PyUntypedArray>()?)
 }
 
 fn read_untyped_array<'py, T: Element + OmFileArrayDataType + Clone + Zero>(
     reader: &OmFileReader<impl OmFileReaderBackend>,
-    read_ranges: Vec<std::ops::Range<u64>>,
+    plan: ReadPlan,
+    squeeze: bool,
+    io_hints: IoHints,
     py: Python<'py>,
 ) -> PyResult<Bound<'py, PyUntypedArray>> {
     let array = reader
-        .read::<T>(&read_ranges, None, None)
+        .read::<T>(&plan.ranges, io_hints.io_size_max, io_hints.io_size_merge)
+        .map_err(convert_omfilesrs_error)?;
+    // Strided slices are read as their covering range and subsampled afterwards
+    let array = if plan.is_strided() {
+        array
+            .slice_each_axis(|axis| Slice::new(0, None, plan.steps[axis.axis.index()] as isize))
+            .to_owned()
+    } else {
+        array
+    };
+    // Like numpy basic indexing, integer indexed axes are removed from the output
+    // while slices keep their axis even if they select a single element
+    let array = if squeeze {
+        plan.integer_axes
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, &is_integer)| is_integer)
+            .fold(array, |array, (axis, _)| {
+                array.index_axis_move(Axis(axis), 0)
+            })
+    } else {
+        array
+    };
+    Ok(array.into_pyarray(py).as_untyped().to_owned()) // FIXME: avoid cloning?
+}
+
+/// Integer arrays spanning at most this many values get one histogram bin per value.
+const MAX_VALUE_BINS: usize = 256;
+
+fn histogram_typed<T: OmFileArrayDataType + Clone + Zero + AsPrimitive<f64>>(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    read_ranges: Vec<Range<u64>>,
+    bins: Option<usize>,
+    range: Option<(f64, f64)>,
+    is_integer: bool,
+    io_hints: IoHints,
+) -> PyResult<(Vec<u64>, Vec<f64>)> {
+    let array = reader
+        .read::<T>(&read_ranges, io_hints.io_size_max, io_hints.io_size_merge)
+        .map_err(convert_omfilesrs_error)?;
+    compute_histogram(
+        array.iter().map(|value| value.as_()),
+        bins,
+        range,
+        is_integer,
+    )
+}
+
+fn reduce_typed<T: OmFileArrayDataType + Clone + Zero + AsPrimitive<f64>>(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    read_ranges: Vec<Range<u64>>,
+    io_hints: IoHints,
+) -> PyResult<(f64, f64, f64)> {
+    let array = reader
+        .read::<T>(&read_ranges, io_hints.io_size_max, io_hints.io_size_merge)
+        .map_err(convert_omfilesrs_error)?;
+    Ok(compute_min_max_mean(array.iter().map(|value| value.as_())))
+}
+
+/// Minimum, maximum and mean of the non-NaN values, like `numpy.nanmin`, `nanmax` and `nanmean`.
+fn compute_min_max_mean(values: impl Iterator<Item = f64>) -> (f64, f64, f64) {
+    let (min, max, sum, count) = values.filter(|value| !value.is_nan()).fold(
+        (f64::INFINITY, f64::NEG_INFINITY, 0.0, 0u64),
+        |(min, max, sum, count), value| (min.min(value), max.max(value), sum + value, count + 1),
+    );
+    if count == 0 {
+        return (f64::NAN, f64::NAN, f64::NAN);
+    }
+    (min, max, sum / count as f64)
+}
+
+/// Bins the non-NaN values. Follows the conventions of `numpy.histogram`:
+/// all bins are half-open except the last one, which includes the upper edge.
+fn compute_histogram(
+    values: impl Iterator<Item = f64> + Clone,
+    bins: Option<usize>,
+    range: Option<(f64, f64)>,
+    is_integer: bool,
+) -> PyResult<(Vec<u64>, Vec<f64>)> {
+    let values = values.filter(|value| !value.is_nan());
+    let (low, high) = match range {
+        Some(range) => range,
+        None => {
+            let (low, high) = values
+                .clone()
+                .fold(None, |bounds: Option<(f64, f64)>, value| match bounds {
+                    Some((low, high)) => Some((low.min(value), high.max(value))),
+                    None => Some((value, value)),
+                })
+                .unwrap_or((0.0, 1.0));
+            // Like numpy, infinite values require an explicit range
+            if !low.is_finite() || !high.is_finite() {
+                return Err(PyValueError::new_err(format!(
+                    "autodetected range of [{}, {}] is not finite",
+                    low, high
+                )));
+            }
+            (low, high)
+        }
+    };
+
+    let (bins, low, high) = match bins {
+        Some(bins) => (bins, low, high),
+        None if is_integer => match integer_span(low, high) {
+            // A given range is split into unit bins and keeps its upper edge
+            Some(span) if range.is_some() => (span.max(1), low, high),
+            // One bin per value, the last one ends one above the maximum
+            Some(span) => (span + 1, low, low + (span + 1) as f64),
+            None => (10, low, high),
+        },
+        None => (10, low, high),
+    };
+    // Like numpy, an empty range is widened around its value
+    let (low, high) = if low == high {
+        (low - 0.5, high + 0.5)
+    } else {
+        (low, high)
+    };
+
+    let width = (high - low) / bins as f64;
+    let edges = (0..=bins)
+        .map(|i| low + width * i as f64)
+        .collect::<Vec<_>>();
+    let mut counts = vec![0u64; bins];
+    for value in values {
+        if value < low || value > high {
+            continue;
+        }
+        let bin = (((value - low) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    Ok((counts, edges))
+}
+
+/// Number of unit steps from `low` to `high` if one bin per value stays within
+/// `MAX_VALUE_BINS`. Infinite or huge spans, e.g. of the full int64 range, yield None.
+fn integer_span(low: f64, high: f64) -> Option<usize> {
+    let span = high - low;
+    if !span.is_finite() || span < 0.0 || span >= MAX_VALUE_BINS as f64 {
+        return None;
+    }
+    let span = span as usize;
+    span.checked_add(1)
+        .filter(|value_count| *value_count <= MAX_VALUE_BINS)
+        .map(|_| span)
+}
+
+/// Returns the first direct child of `reader` with the given name.
+fn find_child<Backend: OmFileReaderBackend>(
+    reader: &OmFileReader<Backend>,
+    name: &str,
+) -> Option<OmFileReader<Backend>> {
+    (0..reader.number_of_children())
+        .filter_map(|index| reader.get_child(index))
+        .find(|child| child.get_name().as_deref() == Some(name))
+}
+
+/// Walks down the slash separated `path` of child names, starting at the children of `reader`.
+/// Returns None if any segment is missing or the path is empty.
+fn find_variable_by_path<Backend: OmFileReaderBackend>(
+    reader: &OmFileReader<Backend>,
+    path: &str,
+) -> Option<OmFileReader<Backend>> {
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let mut variable = find_child(reader, segments.next()?)?;
+    for segment in segments {
+        variable = find_child(&variable, segment)?;
+    }
+    Some(variable)
+}
+
+/// Recursively collects the slash separated paths of `reader` and all its descendants.
+fn collect_variable_paths(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    parent_path: &str,
+    paths: &mut Vec<String>,
+) {
+    let name = reader.get_name().unwrap_or_default();
+    let path = if parent_path.is_empty() {
+        name
+    } else {
+        format!("{}/{}", parent_path, name)
+    };
+
+    for index in 0..reader.number_of_children() {
+        if let Some(child) = reader.get_child(index) {
+            collect_variable_paths(&child, &path, paths);
+        }
+    }
+    paths.push(path);
+}
+
+fn collect_scalars(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    parent_path: &str,
+    py: Python<'_>,
+    scalars: &mut HashMap<String, PyObject>,
+) -> PyResult<()> {
+    let name = reader.get_name().unwrap_or_default();
+    let path = if parent_path.is_empty() {
+        name
+    } else {
+        format!("{}/{}", parent_path, name)
+    };
+
+    for index in 0..reader.number_of_children() {
+        if let Some(child) = reader.get_child(index) {
+            collect_scalars(&child, &path, py, scalars)?;
+        }
+    }
+    let dtype = reader.data_type();
+    if !is_array(&dtype) && !matches!(dtype, DataType::None) {
+        scalars.insert(path, read_scalar_object(reader, py)?);
+    }
+    Ok(())
+}
+
+/// Checks that the dimensions and chunk dimensions of an array variable are consistent.
+fn validate_array_metadata(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    path: &str,
+    problems: &mut Vec<String>,
+) {
+    if !is_array(&reader.data_type()) {
+        return;
+    }
+    let dimensions = reader.get_dimensions();
+    let chunks = reader.get_chunk_dimensions();
+
+    if dimensions.len() != chunks.len() {
+        problems.push(format!(
+            "{}: {} dimensions but {} chunk dimensions",
+            path,
+            dimensions.len(),
+            chunks.len()
+        ));
+    }
+    if chunks.iter().any(|&chunk| chunk == 0) {
+        problems.push(format!("{}: chunk dimensions {:?} contain 0", path, chunks));
+    }
+}
+
+/// Magic number at the start of every om file.
+const OM_MAGIC: &[u8] = b"OM";
+/// Newest om file format version this library can read.
+/// Versions 1 and 2 are legacy files with a header only, version 3 files have a trailer.
+const OM_MAX_VERSION: u8 = 3;
+
+/// Checks the om magic number and returns the format version stored after it.
+fn read_format_version(backend: &BackendImpl) -> PyResult<u8> {
+    let header_size = OM_MAGIC.len() + 1;
+    if backend.count() < OM_MAGIC.len()
+        || backend
+            .get_bytes_owned(0, OM_MAGIC.len() as u64)
+            .map_err(convert_omfilesrs_error)?
+            != OM_MAGIC
+    {
+        return Err(OmNotAnOmFileError::new_err(
+            "Not an om file: missing om magic number",
+        ));
+    }
+    if backend.count() < header_size {
+        return Err(OmTruncatedFileError::new_err(
+            "Truncated om file: missing format version",
+        ));
+    }
+    let header = backend
+        .get_bytes_owned(0, header_size as u64)
         .map_err(convert_omfilesrs_error)?;
-    // We only add dimensions that are no singleton dimensions to the output shape
-    // This is basically a dimensional squeeze and it is the same behavior as numpy
-    Ok(array.squeeze().into_pyarray(py).as_untyped().to_owned()) // FIXME: avoid cloning?
+    Ok(header[OM_MAGIC.len()])
+}
+
+/// Reads all scalar children of a variable into a name to value map.
+/// Children holding binding metadata like categories or dimension names and the file
+/// metadata returned by `file_metadata` are skipped.
+fn scalar_attributes(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    py: Python<'_>,
+) -> PyResult<HashMap<String, PyObject>> {
+    let mut attrs = HashMap::new();
+    for index in 0..reader.number_of_children() {
+        let Some(child) = reader.get_child(index) else {
+            continue;
+        };
+        let dtype = child.data_type();
+        if is_array(&dtype) || matches!(dtype, DataType::None) {
+            continue;
+        }
+        let Some(name) = child.get_name() else {
+            continue;
+        };
+        if [
+            CATEGORIES_KEY,
+            DIMENSIONS_KEY,
+            STORED_DTYPE_KEY,
+            FILL_VALUE_KEY,
+        ]
+        .contains(&name.as_str())
+            || FILE_METADATA_KEYS.contains(&name.as_str())
+        {
+            continue;
+        }
+        attrs.insert(name, read_scalar_object(&child, py)?);
+    }
+    Ok(attrs)
+}
+
+/// The `ArrayWithAttrs` named tuple returned by `read_with_attrs`, created once per interpreter.
+pub fn array_with_attrs_type(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static ARRAY_WITH_ATTRS: GILOnceCell<PyObject> = GILOnceCell::new();
+    ARRAY_WITH_ATTRS
+        .get_or_try_init(py, || {
+            py.import("collections")?
+                .call_method1("namedtuple", ("ArrayWithAttrs", ["array", "attrs"]))?
+                .into_py_any(py)
+        })
+        .map(|cls| cls.bind(py))
+}
+
+fn read_scalar_object(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    py: Python<'_>,
+) -> PyResult<PyObject> {
+    match reader.data_type() {
+        DataType::Int8 => read_scalar_value::<i8>(reader, py),
+        DataType::Uint8 => read_scalar_value::<u8>(reader, py),
+        DataType::Int16 => read_scalar_value::<i16>(reader, py),
+        DataType::Uint16 => read_scalar_value::<u16>(reader, py),
+        DataType::Int32 => read_scalar_value::<i32>(reader, py),
+        DataType::Uint32 => read_scalar_value::<u32>(reader, py),
+        DataType::Int64 => read_scalar_value::<i64>(reader, py),
+        DataType::Uint64 => read_scalar_value::<u64>(reader, py),
+        DataType::Float => read_scalar_value::<f32>(reader, py),
+        DataType::Double => read_scalar_value::<f64>(reader, py),
+        DataType::String => read_scalar_value::<String>(reader, py),
+        _ => Err(PyValueError::new_err(format!(
+            "Variable {} is not a scalar",
+            reader.get_name().unwrap_or_default()
+        ))),
+    }
+}
+
+fn read_scalar_value<T>(
+    reader: &OmFileReader<impl OmFileReaderBackend>,
+    py: Python<'_>,
+) -> PyResult<PyObject>
+where
+    T: OmFileScalarDataType + for<'py> IntoPyObject<'py>,
+{
+    reader
+        .read_scalar::<T>()
+        .ok_or_else(|| PyValueError::new_err("Failed to read scalar value"))?
+        .into_py_any(py)
 }
 
 /// Concrete wrapper type for the backend implementation, delegating to the appropriate backend
 enum BackendImpl {
     Mmap(MmapFile),
     FsSpec(FsSpecBackend),
+    SubRange(SubRangeBackend<MmapFile>),
+    InMemory(InMemoryBackend),
+    Http(HttpBackend),
+    Cached(CachedBackend<BackendImpl>),
+}
+
+impl BackendImpl {
+    /// Counters of the caches of this backend. All zero if the backend does not cache.
+    fn cache_stats(&self) -> CacheStats {
+        match self {
+            BackendImpl::Mmap(_)
+            | BackendImpl::SubRange(_)
+            | BackendImpl::InMemory(_)
+            | BackendImpl::Http(_) => CacheStats::default(),
+            BackendImpl::FsSpec(backend) => backend.cache_stats(),
+            BackendImpl::Cached(backend) => backend.cache_stats(),
+        }
+    }
+
+    /// Whether the memory mapped file was deleted or replaced after it was opened.
+    /// Always false for backends which do not map a local file.
+    fn was_deleted(&self) -> bool {
+        match self {
+            BackendImpl::Mmap(backend) => backend.was_deleted(),
+            BackendImpl::SubRange(backend) => backend.backend().was_deleted(),
+            BackendImpl::Cached(backend) => backend.backend().was_deleted(),
+            BackendImpl::FsSpec(_) | BackendImpl::InMemory(_) | BackendImpl::Http(_) => false,
+        }
+    }
+
+    fn release_prefetch(&self) {
+        match self {
+            BackendImpl::Mmap(_)
+            | BackendImpl::SubRange(_)
+            | BackendImpl::InMemory(_)
+            | BackendImpl::Http(_) => {}
+            BackendImpl::FsSpec(backend) => backend.release_prefetch(),
+            BackendImpl::Cached(backend) => backend.release_prefetch(),
+        }
+    }
 }
 
 impl OmFileReaderBackend for BackendImpl {
@@ -198,6 +2145,10 @@ impl OmFileReaderBackend for BackendImpl {
         to match self {
             BackendImpl::Mmap(backend) => backend,
             BackendImpl::FsSpec(backend) => backend,
+            BackendImpl::SubRange(backend) => backend,
+            BackendImpl::InMemory(backend) => backend,
+            BackendImpl::Http(backend) => backend,
+            BackendImpl::Cached(backend) => backend,
         } {
             fn count(&self) -> usize;
             fn needs_prefetch(&self) -> bool;
@@ -223,7 +2174,7 @@ mod tests {
         pyo3::prepare_freethreaded_python();
 
         Python::with_gil(|py| {
-            let reader = OmFilePyReader::from_path(file_path).unwrap();
+            let reader = OmFilePyReader::from_path(file_path, false, true, 0, None).unwrap();
             let ranges = ArrayIndex(vec![
                 IndexType::Slice {
                     start: Some(0),
@@ -236,7 +2187,7 @@ mod tests {
                     step: None,
                 },
             ]);
-            let data = reader.__getitem__(py, ranges).expect("Could not get item!");
+            let data = reader.read_index(py, ranges).expect("Could not get item!");
             let data = data
                 .downcast::<PyArrayDyn<f32>>()
                 .expect("Could not downcast to PyArrayDyn<f32>");
@@ -254,4 +2205,67 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_compute_histogram() {
+        let values = [1.0, 2.0, 2.0, f64::NAN, 4.0];
+
+        let (counts, edges) =
+            compute_histogram(values.iter().copied(), Some(3), None, false).unwrap();
+        assert_eq!(counts, vec![1, 2, 1]);
+        assert_eq!(edges, vec![1.0, 2.0, 3.0, 4.0]);
+
+        // Integers are counted per value by default
+        let (counts, edges) = compute_histogram(values.iter().copied(), None, None, true).unwrap();
+        assert_eq!(counts, vec![1, 2, 0, 1]);
+        assert_eq!(edges, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        let (counts, _) =
+            compute_histogram(values.iter().copied(), Some(2), Some((0.0, 2.0)), false).unwrap();
+        assert_eq!(counts, vec![0, 3]);
+    }
+
+    #[test]
+    fn test_compute_histogram_edge_cases() {
+        // Spans that do not fit into one bin per value fall back to 10 bins
+        let values = [i64::MIN as f64, 0.0, i64::MAX as f64];
+        let (counts, edges) = compute_histogram(values.iter().copied(), None, None, true).unwrap();
+        assert_eq!(counts, vec![1, 0, 0, 0, 0, 1, 0, 0, 0, 1]);
+        assert_eq!(edges.len(), 11);
+        assert_eq!(edges[0], i64::MIN as f64);
+        assert_eq!(edges[5], 0.0);
+        assert_eq!(edges[10], i64::MAX as f64);
+
+        let values = [1.0, 1e20];
+        let (counts, edges) = compute_histogram(values.iter().copied(), None, None, false).unwrap();
+        assert_eq!(counts, vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!((edges[0], edges[10]), (1.0, 1e20));
+
+        // Infinite values need an explicit range, which excludes them
+        let values = [1.0, f64::INFINITY, 1e20, f64::NEG_INFINITY];
+        assert!(compute_histogram(values.iter().copied(), None, None, false).is_err());
+        let (counts, edges) =
+            compute_histogram(values.iter().copied(), Some(2), Some((0.0, 2.0)), false).unwrap();
+        assert_eq!(counts, vec![0, 1]);
+        assert_eq!(edges, vec![0.0, 1.0, 2.0]);
+
+        // A given range keeps its upper edge, values above it are not counted
+        let values = [0.0, 1.0, 2.0, 3.0];
+        let (counts, edges) =
+            compute_histogram(values.iter().copied(), None, Some((0.0, 2.0)), true).unwrap();
+        assert_eq!(counts, vec![1, 2]);
+        assert_eq!(edges, vec![0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_compute_min_max_mean() {
+        let values = [1.0, f64::NAN, -2.0, 4.0];
+        assert_eq!(
+            compute_min_max_mean(values.iter().copied()),
+            (-2.0, 4.0, 1.0)
+        );
+
+        let (min, max, mean) = compute_min_max_mean([f64::NAN].iter().copied());
+        assert!(min.is_nan() && max.is_nan() && mean.is_nan());
+    }
 }
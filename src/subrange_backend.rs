@@ -0,0 +1,131 @@
+use omfiles_rs::backend::backends::OmFileReaderBackend;
+use omfiles_rs::errors::OmFilesRsError;
+use pyo3::{exceptions::PyValueError, PyResult};
+
+/// Presents the window `[base_offset, base_offset + length)` of another backend
+/// as a standalone file. This allows reading .om files embedded in a larger container.
+pub struct SubRangeBackend<Backend> {
+    backend: Backend,
+    base_offset: u64,
+    length: u64,
+}
+
+impl<Backend: OmFileReaderBackend> SubRangeBackend<Backend> {
+    /// If `length` is None, the window extends to the end of the underlying backend.
+    pub fn new(backend: Backend, base_offset: u64, length: Option<u64>) -> PyResult<Self> {
+        let total_size = backend.count() as u64;
+        if base_offset > total_size {
+            return Err(PyValueError::new_err(format!(
+                "Base offset {} exceeds the file size {}",
+                base_offset, total_size
+            )));
+        }
+        let length = length.unwrap_or(total_size - base_offset);
+        if base_offset
+            .checked_add(length)
+            .is_none_or(|end| end > total_size)
+        {
+            return Err(PyValueError::new_err(format!(
+                "Range of length {} at offset {} exceeds the file size {}",
+                length, base_offset, total_size
+            )));
+        }
+
+        Ok(Self {
+            backend,
+            base_offset,
+            length,
+        })
+    }
+
+    pub fn backend(&self) -> &Backend {
+        &self.backend
+    }
+
+    /// Translates a read of the window to the underlying backend. Reads past the end of
+    /// the window are rejected instead of returning bytes of the surrounding file.
+    fn window_offset(&self, offset: u64, count: u64) -> Result<u64, OmFilesRsError> {
+        if offset
+            .checked_add(count)
+            .is_none_or(|end| end > self.length)
+        {
+            return Err(OmFilesRsError::DecoderError(format!(
+                "Read of {} bytes at offset {} exceeds the range of length {}",
+                count, offset, self.length
+            )));
+        }
+        Ok(offset + self.base_offset)
+    }
+}
+
+impl<Backend: OmFileReaderBackend> OmFileReaderBackend for SubRangeBackend<Backend> {
+    fn count(&self) -> usize {
+        self.length as usize
+    }
+
+    fn needs_prefetch(&self) -> bool {
+        self.backend.needs_prefetch()
+    }
+
+    fn prefetch_data(&self, offset: usize, count: usize) {
+        self.backend
+            .prefetch_data(offset + self.base_offset as usize, count)
+    }
+
+    fn pre_read(&self, offset: usize, count: usize) -> Result<(), OmFilesRsError> {
+        self.backend
+            .pre_read(offset + self.base_offset as usize, count)
+    }
+
+    fn get_bytes(&self, offset: u64, count: u64) -> Result<&[u8], OmFilesRsError> {
+        self.backend
+            .get_bytes(self.window_offset(offset, count)?, count)
+    }
+
+    fn get_bytes_owned(&self, offset: u64, count: u64) -> Result<Vec<u8>, OmFilesRsError> {
+        self.backend
+            .get_bytes_owned(self.window_offset(offset, count)?, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_test_binary_file;
+    use omfiles_rs::backend::mmapfile::{MmapFile, Mode};
+    use std::{error::Error, fs::File};
+
+    #[test]
+    fn test_subrange_backend() -> Result<(), Box<dyn Error>> {
+        let file_name = "test_subrange_backend.om";
+        create_test_binary_file!(file_name)?;
+        let file_handle = File::open(format!("test_files/{}", file_name))?;
+        let mmap = MmapFile::new(file_handle, Mode::ReadOnly)?;
+
+        let backend = SubRangeBackend::new(mmap, 4, Some(40))?;
+        assert_eq!(backend.count(), 40);
+        assert_eq!(backend.get_bytes(0, 4)?, &[4, 130, 0, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subrange_backend_out_of_bounds() -> Result<(), Box<dyn Error>> {
+        let file_name = "test_subrange_backend_bounds.om";
+        create_test_binary_file!(file_name)?;
+        let open = || -> Result<MmapFile, Box<dyn Error>> {
+            let file_handle = File::open(format!("test_files/{}", file_name))?;
+            Ok(MmapFile::new(file_handle, Mode::ReadOnly)?)
+        };
+
+        assert!(SubRangeBackend::new(open()?, 100, Some(100)).is_err());
+        assert!(SubRangeBackend::new(open()?, 4, Some(u64::MAX)).is_err());
+
+        let backend = SubRangeBackend::new(open()?, 4, Some(40))?;
+        assert!(backend.get_bytes(36, 4).is_ok());
+        assert!(backend.get_bytes(38, 4).is_err());
+        assert!(backend.get_bytes_owned(u64::MAX, 1).is_err());
+
+        Ok(())
+    }
+}
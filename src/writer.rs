@@ -1,131 +1,867 @@
-use crate::errors::convert_omfilesrs_error;
+use crate::{
+    compression::{CompressionArg, PyCompression, PyCompressionType},
+    errors::{convert_io_error, convert_omfilesrs_error},
+    hierarchy::OmVariable,
+    metadata::{
+        CATEGORIES_KEY, DIMENSIONS_KEY, FILE_METADATA_KEYS, FILL_VALUE_KEY, STORED_DTYPE_KEY,
+        SUPPORTED_CALENDARS,
+    },
+    reader::OmFilePyReader,
+};
 use numpy::{
-    dtype, Element, PyArrayDescrMethods, PyArrayDyn, PyArrayMethods, PyReadonlyArrayDyn,
-    PyUntypedArray, PyUntypedArrayMethods,
+    dtype, Element, PyArrayDescr, PyArrayDescrMethods, PyArrayDyn, PyArrayMethods,
+    PyReadonlyArrayDyn, PyUntypedArray, PyUntypedArrayMethods,
 };
 use omfiles_rs::{
-    core::compression::CompressionType, core::data_types::OmFileArrayDataType,
-    io::writer::OmFileWriter,
+    backend::backends::OmFileWriterBackend,
+    core::compression::CompressionType,
+    core::data_types::OmFileArrayDataType,
+    errors::OmFilesRsError,
+    io::writer::{OmFileWriter, OmFileWriterArrayFinalized, OmOffsetSize},
+};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
+    prelude::*,
+    types::{IntoPyDict, PyBytes, PySlice, PyTuple},
 };
-use pyo3::{exceptions::PyValueError, prelude::*};
-use std::fs::File;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    sync::{Arc, Mutex},
+};
+
+/// Numpy dtypes accepted by `OmFilePyWriter.write_array`.
+/// Keep in sync with the dtype dispatch in `write_array`. float16 is stored as float32.
+const SUPPORTED_DTYPES: [&str; 11] = [
+    "float32", "float64", "int32", "int64", "uint32", "uint64", "int8", "uint8", "int16", "uint16",
+    "float16",
+];
+
+/// Arrays up to this number of elements are verified completely with `verify=True`.
+const VERIFY_FULL_LIMIT: usize = 1 << 24;
+
+/// Number of elements targeted per chunk if `write_array` is called without chunks.
+const DEFAULT_CHUNK_ELEMENTS: u64 = 1 << 16;
+
+/// Returns the numpy dtype strings accepted by `OmFilePyWriter.write_array`.
+#[pyfunction]
+pub fn supported_dtypes() -> Vec<&'static str> {
+    SUPPORTED_DTYPES.to_vec()
+}
 
-#[derive(Clone)]
-pub enum PyCompressionType {
-    PforDelta2dInt16,
-    FpxXor2d,
-    PforDelta2d,
-    PforDelta2dInt16Logarithmic,
+/// Destination of an `OmFilePyWriter`, a file or a buffer in memory.
+enum WriterBackend {
+    File(File),
+    /// Shared with the writer, so the bytes can be returned after the writer is dropped
+    Memory(Arc<Mutex<Vec<u8>>>),
 }
 
-impl PyCompressionType {
-    fn to_omfilesrs(&self) -> CompressionType {
+impl OmFileWriterBackend for WriterBackend {
+    fn write(&mut self, data: &[u8]) -> Result<(), OmFilesRsError> {
         match self {
-            PyCompressionType::PforDelta2dInt16 => CompressionType::PforDelta2dInt16,
-            PyCompressionType::FpxXor2d => CompressionType::FpxXor2d,
-            PyCompressionType::PforDelta2d => CompressionType::PforDelta2d,
-            PyCompressionType::PforDelta2dInt16Logarithmic => {
-                CompressionType::PforDelta2dInt16Logarithmic
+            WriterBackend::File(file) => OmFileWriterBackend::write(file, data),
+            WriterBackend::Memory(buffer) => {
+                buffer.lock().unwrap().extend_from_slice(data);
+                Ok(())
             }
         }
     }
 
-    fn from_str(s: &str) -> PyResult<Self> {
-        match s {
-            "pfor_delta_2d_int16" => Ok(PyCompressionType::PforDelta2dInt16),
-            "fpx_xor_2d" => Ok(PyCompressionType::FpxXor2d),
-            "pfor_delta_2d" => Ok(PyCompressionType::PforDelta2d),
-            "pfor_delta_2d_int16_logarithmic" => Ok(PyCompressionType::PforDelta2dInt16Logarithmic),
-            _ => Err(PyValueError::new_err(format!(
-                "Unsupported compression type: {}",
-                s
-            ))),
+    fn synchronize(&self) -> Result<(), OmFilesRsError> {
+        match self {
+            WriterBackend::File(file) => OmFileWriterBackend::synchronize(file),
+            WriterBackend::Memory(_) => Ok(()),
         }
     }
 }
 
 #[pyclass]
 pub struct OmFilePyWriter {
-    file_writer: OmFileWriter<File>,
+    /// `None` once the writer is closed
+    file_writer: Option<OmFileWriter<WriterBackend>>,
+    /// The path being written, a temporary file for atomic writers. Empty in memory.
+    file_path: String,
+    /// The written bytes of a writer created with `in_memory`
+    buffer: Option<Arc<Mutex<Vec<u8>>>>,
+    /// The final path of an atomic writer, the temporary file is renamed to it on `close`
+    target_path: Option<String>,
+    /// File metadata scalars which are attached as children to the root variable
+    root_children: Vec<OmOffsetSize>,
+    /// Records of written arrays and groups which additionally have the file metadata
+    /// as children, keyed by the offset and size of the variable. Empty without metadata.
+    root_records: HashMap<(u64, u64), OmOffsetSize>,
+    /// Whether a trailer pointing to a root variable has been written
+    has_root: bool,
 }
 
 #[pymethods]
 impl OmFilePyWriter {
     #[new]
-    fn new(file_path: &str) -> PyResult<Self> {
-        let file_handle = File::create(file_path)?;
-        let writer = OmFileWriter::new(file_handle, 8 * 1024); // initial capacity of 8KB
-        Ok(Self {
-            file_writer: writer,
-        })
+    #[pyo3(signature = (file_path, metadata=None, atomic=false))]
+    fn new(
+        file_path: &str,
+        metadata: Option<HashMap<String, Bound<'_, PyAny>>>,
+        atomic: bool,
+    ) -> PyResult<Self> {
+        let (write_path, target_path) = if atomic {
+            let temp_path = format!("{}.tmp.{}", file_path, std::process::id());
+            (temp_path, Some(file_path.to_string()))
+        } else {
+            (file_path.to_string(), None)
+        };
+        let file_handle =
+            File::create(&write_path).map_err(|e| convert_io_error(e, &write_path))?;
+        Self::with_backend(
+            WriterBackend::File(file_handle),
+            write_path,
+            target_path,
+            None,
+            metadata,
+        )
+    }
+
+    /// Creates a writer which builds the file in memory. The file is returned by `to_bytes`.
+    #[staticmethod]
+    #[pyo3(signature = (metadata=None))]
+    fn in_memory(metadata: Option<HashMap<String, Bound<'_, PyAny>>>) -> PyResult<Self> {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        Self::with_backend(
+            WriterBackend::Memory(buffer.clone()),
+            String::new(),
+            None,
+            Some(buffer),
+            metadata,
+        )
+    }
+
+    /// Closes a writer created with `in_memory` and returns the complete file.
+    /// Can be called again after the writer is closed.
+    fn to_bytes<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        self.memory_buffer()?;
+        if !self.has_root {
+            return Err(PyRuntimeError::new_err(
+                "No array or group was written and no root was set, so the file has no root variable",
+            ));
+        }
+        self.close()?;
+        self.get_bytes(py)
+    }
+
+    /// Returns the complete file of a closed writer created with `in_memory`, e.g. after
+    /// a `with` block.
+    fn get_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let buffer = self.memory_buffer()?;
+        if self.file_writer.is_some() {
+            return Err(PyValueError::new_err(
+                "The writer is still open, call close or to_bytes first",
+            ));
+        }
+        let bytes = buffer.lock().unwrap();
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    /// Closes the file. Atomic writers rename the temporary file to the target path.
+    /// Further writes raise a `ValueError`. Closing a closed writer does nothing.
+    fn close(&mut self) -> PyResult<()> {
+        // Dropping the writer closes the file handle
+        if self.file_writer.take().is_none() {
+            return Ok(());
+        }
+        if let Some(target_path) = self.target_path.take() {
+            std::fs::rename(&self.file_path, &target_path)
+                .map_err(|e| convert_io_error(e, &target_path))?;
+            self.file_path = target_path;
+        }
+        Ok(())
+    }
+
+    /// Whether `close` has been called or a `with` block was left.
+    #[getter]
+    fn closed(&self) -> bool {
+        self.file_writer.is_none()
+    }
+
+    /// Makes `variable` the root of the file by writing a new trailer pointing to it.
+    /// By default the last written array or group is the root.
+    fn set_root(&mut self, variable: &OmVariable) -> PyResult<()> {
+        let root = self
+            .root_records
+            .get(&(variable.offset, variable.size))
+            .copied()
+            .unwrap_or_else(|| variable.offset_size());
+        let file_writer = self.file_writer.as_mut().ok_or_else(writer_closed)?;
+        file_writer
+            .write_trailer(root)
+            .map_err(convert_omfilesrs_error)?;
+        self.has_root = true;
+        Ok(())
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Closes the writer at the end of a `with` block. If the block raised, the file is
+    /// closed without renaming the temporary file of an atomic writer.
+    fn __exit__(
+        &mut self,
+        exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        if exc_type.is_some() {
+            self.file_writer = None;
+            return Ok(false);
+        }
+        if !self.has_root {
+            self.file_writer = None;
+            return Err(PyRuntimeError::new_err(
+                "No array or group was written and no root was set, so the file has no root variable",
+            ));
+        }
+        self.close()?;
+        Ok(false)
     }
 
     #[pyo3(
-            text_signature = "(data, chunks, /, *, scale_factor=1.0, add_offset=0.0, compression='pfor_delta_2d', name='data')",
-            signature = (data, chunks, scale_factor=None, add_offset=None, compression=None, name=None)
+            text_signature = "(data, chunks=None, /, *, scale_factor=1.0, add_offset=0.0, compression='pfor_delta_2d', name='data', children=None, categories=None, dimensions=None, fill_value=None, verify=False)",
+            signature = (data, chunks=None, scale_factor=None, add_offset=None, compression=None, name=None, children=None, categories=None, dimensions=None, fill_value=None, verify=false)
         )]
     fn write_array(
         &mut self,
-        data: &Bound<'_, PyUntypedArray>,
-        chunks: Vec<u64>,
-        scale_factor: Option<f32>,
-        add_offset: Option<f32>,
-        compression: Option<&str>,
+        data: &Bound<'_, PyAny>,
+        chunks: Option<Vec<u64>>,
+        scale_factor: Option<f64>,
+        add_offset: Option<f64>,
+        compression: Option<CompressionArg>,
         name: Option<&str>,
-    ) -> PyResult<()> {
-        let element_type = data.dtype();
+        children: Option<Vec<OmVariable>>,
+        categories: Option<BTreeMap<i64, String>>,
+        dimensions: Option<Vec<String>>,
+        fill_value: Option<&Bound<'_, PyAny>>,
+        verify: bool,
+    ) -> PyResult<OmVariable> {
+        let data = as_numpy_array(data)?;
         let py = data.py();
+        let chunks = chunks.unwrap_or_else(|| default_chunks(data.shape()));
 
-        let scale_factor = scale_factor.unwrap_or(1.0);
-        let add_offset = add_offset.unwrap_or(0.0);
-        let compression = compression
-            .map(|s| PyCompressionType::from_str(s))
-            .transpose()?
-            .unwrap_or(PyCompressionType::PforDelta2d)
-            .to_omfilesrs();
+        let (py_compression, scale_factor, add_offset) = compression_arguments(
+            compression,
+            scale_factor,
+            add_offset,
+            data.dtype().is_equiv_to(&dtype::<f64>(py)),
+        )?;
+
+        // All arguments are validated before the first child is written, so that a
+        // rejected call leaves no orphaned scalars in the file.
+        // The codecs have no half precision support, float16 is widened losslessly and
+        // marked so that readers cast it back
+        let is_float16 = data.dtype().is_equiv_to(&PyArrayDescr::new(py, "float16")?);
+        let data = if is_float16 {
+            data.call_method1("astype", ("float32",))?
+                .downcast_into::<PyUntypedArray>()?
+        } else {
+            data
+        };
+        let element_type = data.dtype();
+        let type_name = element_type.getattr("name")?.extract::<String>()?;
+        if !SUPPORTED_DTYPES.contains(&type_name.as_str()) {
+            return Err(PyValueError::new_err(format!(
+                "Unsupported data type: {:?}. Supported data types are: {}",
+                element_type,
+                SUPPORTED_DTYPES.join(", ")
+            )));
+        }
+        let dimension_sizes = data
+            .shape()
+            .iter()
+            .map(|&dim| dim as u64)
+            .collect::<Vec<_>>();
+        validate_chunks(&dimension_sizes, &chunks)?;
+        if matches!(
+            py_compression,
+            PyCompressionType::PforDelta2dInt16Logarithmic
+        ) {
+            check_non_negative(&data)?;
+        }
+        if categories.is_some() && !matches!(element_type.kind(), b'i' | b'u') {
+            return Err(PyValueError::new_err(
+                "Categories can only be attached to integer arrays",
+            ));
+        }
+        if let Some(dimensions) = &dimensions {
+            if dimensions.len() != data.ndim() {
+                return Err(PyValueError::new_err(format!(
+                    "Got {} dimension names for an array with {} dimensions",
+                    dimensions.len(),
+                    data.ndim()
+                )));
+            }
+        }
+        let fill_value = fill_value
+            .map(|fill_value| -> PyResult<_> {
+                // Stored with the element type of the array, so e.g. 1.5 is rejected for int arrays
+                let fill_value = py.import("numpy")?.call_method1("asarray", (fill_value,))?;
+                let cast = fill_value.call_method1("astype", (&element_type,))?;
+                if matches!(element_type.kind(), b'i' | b'u') && !cast.eq(&fill_value)? {
+                    return Err(PyValueError::new_err(format!(
+                        "Fill value {} cannot be represented as {}",
+                        fill_value, element_type
+                    )));
+                }
+                cast.call_method0("item")
+            })
+            .transpose()?;
+
+        let mut children = children.unwrap_or_default();
+        if is_float16 {
+            children.push(self.write_scalar(
+                "float16".into_pyobject(py)?.as_any(),
+                STORED_DTYPE_KEY,
+                None,
+            )?);
+        }
+        if let Some(categories) = categories {
+            // Stored as a JSON object from category value to label
+            let json = py
+                .import("json")?
+                .call_method1("dumps", (categories.into_pyobject(py)?,))?;
+            children.push(self.write_scalar(&json, CATEGORIES_KEY, None)?);
+        }
+        if let Some(dimensions) = dimensions {
+            let json = py.import("json")?.call_method1("dumps", (dimensions,))?;
+            children.push(self.write_scalar(&json, DIMENSIONS_KEY, None)?);
+        }
+        if let Some(fill_value) = fill_value {
+            children.push(self.write_scalar(&fill_value, FILL_VALUE_KEY, None)?);
+        }
+
+        let compression = py_compression.to_omfilesrs();
+        let verify_chunks = chunks.clone();
 
         let name = name.unwrap_or("data");
+        let children = children
+            .iter()
+            .map(OmVariable::offset_size)
+            .collect::<Vec<_>>();
 
-        if element_type.is_equiv_to(&dtype::<f32>(py)) {
+        let variable = if element_type.is_equiv_to(&dtype::<f32>(py)) {
             let array = data.downcast::<PyArrayDyn<f32>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<f64>(py)) {
             let array = data.downcast::<PyArrayDyn<f64>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<i32>(py)) {
             let array = data.downcast::<PyArrayDyn<i32>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<i64>(py)) {
             let array = data.downcast::<PyArrayDyn<i64>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<u32>(py)) {
             let array = data.downcast::<PyArrayDyn<u32>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<u64>(py)) {
             let array = data.downcast::<PyArrayDyn<u64>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<i8>(py)) {
             let array = data.downcast::<PyArrayDyn<i8>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<u8>(py)) {
             let array = data.downcast::<PyArrayDyn<u8>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<i16>(py)) {
             let array = data.downcast::<PyArrayDyn<i16>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else if element_type.is_equiv_to(&dtype::<u16>(py)) {
             let array = data.downcast::<PyArrayDyn<u16>>()?.readonly();
-            self.write_array_internal(array, chunks, scale_factor, add_offset, compression, name)
+            self.write_array_internal(
+                array,
+                chunks,
+                scale_factor,
+                add_offset,
+                compression,
+                name,
+                &children,
+            )
         } else {
             Err(PyValueError::new_err(format!(
-                "Unsupported data type: {:?}",
-                element_type
+                "Unsupported data type: {:?}. Supported data types are: {}",
+                element_type,
+                SUPPORTED_DTYPES.join(", ")
             )))
+        }?;
+
+        if verify {
+            self.verify_array(
+                &data,
+                &variable,
+                &verify_chunks,
+                scale_factor,
+                &py_compression,
+            )?;
         }
+        Ok(variable)
     }
+
+    /// Write an array with scalar attributes, e.g. `{"units": "K"}`. Each attribute is
+    /// written with `write_scalar` and attached as a child of the array.
+    #[pyo3(
+            text_signature = "(data, attrs, chunks=None, /, *, scale_factor=1.0, add_offset=0.0, compression='pfor_delta_2d', name='data', children=None)",
+            signature = (data, attrs, chunks=None, scale_factor=None, add_offset=None, compression=None, name=None, children=None)
+        )]
+    fn write_array_with_attrs(
+        &mut self,
+        data: &Bound<'_, PyAny>,
+        attrs: HashMap<String, Bound<'_, PyAny>>,
+        chunks: Option<Vec<u64>>,
+        scale_factor: Option<f64>,
+        add_offset: Option<f64>,
+        compression: Option<CompressionArg>,
+        name: Option<&str>,
+        children: Option<Vec<OmVariable>>,
+    ) -> PyResult<OmVariable> {
+        let mut children = children.unwrap_or_default();
+        // Sort the keys so the written layout does not depend on dict ordering
+        let mut keys = attrs.keys().collect::<Vec<_>>();
+        keys.sort();
+        for key in keys {
+            children.push(self.write_scalar(&attrs[key], key, None)?);
+        }
+        self.write_array(
+            data,
+            chunks,
+            scale_factor,
+            add_offset,
+            compression,
+            name,
+            Some(children),
+            None,
+            None,
+            None,
+            false,
+        )
+    }
+
+    /// Write an array chunk by chunk without holding the complete array in memory.
+    /// `chunk_iter` yields `(chunk_coords, data)` pairs in row-major order of the chunk
+    /// coordinates, where `data` has the shape of the chunk, which is smaller at the upper
+    /// boundaries of the array.
+    #[pyo3(
+            text_signature = "(chunk_iter, dimensions, chunks, dtype, /, *, scale_factor=1.0, add_offset=0.0, compression='pfor_delta_2d', name='data', children=None)",
+            signature = (chunk_iter, dimensions, chunks, dtype, scale_factor=None, add_offset=None, compression=None, name=None, children=None)
+        )]
+    fn write_array_from_iter(
+        &mut self,
+        chunk_iter: &Bound<'_, PyAny>,
+        dimensions: Vec<u64>,
+        chunks: Vec<u64>,
+        dtype: &str,
+        scale_factor: Option<f64>,
+        add_offset: Option<f64>,
+        compression: Option<CompressionArg>,
+        name: Option<&str>,
+        children: Option<Vec<OmVariable>>,
+    ) -> PyResult<OmVariable> {
+        validate_chunks(&dimensions, &chunks)?;
+        let (compression, scale_factor, add_offset) =
+            compression_arguments(compression, scale_factor, add_offset, dtype == "float64")?;
+        let layout = ArrayLayout {
+            dimensions,
+            chunks,
+            scale_factor,
+            add_offset,
+            compression: compression.to_omfilesrs(),
+        };
+        let name = name.unwrap_or("data");
+        let mut children = children.unwrap_or_default();
+        if dtype == "float16" {
+            let py = chunk_iter.py();
+            children.push(self.write_scalar(
+                "float16".into_pyobject(py)?.as_any(),
+                STORED_DTYPE_KEY,
+                None,
+            )?);
+        }
+        let children = children
+            .iter()
+            .map(OmVariable::offset_size)
+            .collect::<Vec<_>>();
+
+        match dtype {
+            "float32" | "float16" => {
+                self.write_chunks_internal::<f32>(chunk_iter, layout, name, &children)
+            }
+            "float64" => self.write_chunks_internal::<f64>(chunk_iter, layout, name, &children),
+            "int32" => self.write_chunks_internal::<i32>(chunk_iter, layout, name, &children),
+            "int64" => self.write_chunks_internal::<i64>(chunk_iter, layout, name, &children),
+            "uint32" => self.write_chunks_internal::<u32>(chunk_iter, layout, name, &children),
+            "uint64" => self.write_chunks_internal::<u64>(chunk_iter, layout, name, &children),
+            "int8" => self.write_chunks_internal::<i8>(chunk_iter, layout, name, &children),
+            "uint8" => self.write_chunks_internal::<u8>(chunk_iter, layout, name, &children),
+            "int16" => self.write_chunks_internal::<i16>(chunk_iter, layout, name, &children),
+            "uint16" => self.write_chunks_internal::<u16>(chunk_iter, layout, name, &children),
+            _ => Err(PyValueError::new_err(format!(
+                "Unsupported data type: {}. Supported data types are: {}",
+                dtype,
+                SUPPORTED_DTYPES.join(", ")
+            ))),
+        }
+    }
+
+    /// Write a scalar value (str, int or float) which can be attached as a child to other variables.
+    #[pyo3(signature = (value, name, children=None))]
+    fn write_scalar(
+        &mut self,
+        value: &Bound<'_, PyAny>,
+        name: &str,
+        children: Option<Vec<OmVariable>>,
+    ) -> PyResult<OmVariable> {
+        let children = children
+            .unwrap_or_default()
+            .iter()
+            .map(OmVariable::offset_size)
+            .collect::<Vec<_>>();
+        let variable = self.write_scalar_internal(value, name, &children)?;
+        Ok(OmVariable::new(name, &variable))
+    }
+
+    /// Copies the array of `source`, which may belong to another file, with new chunk
+    /// dimensions. Compression, scale factor and offset are kept. The name defaults to the
+    /// name of the source. The complete array is held in memory while it is rewritten.
+    #[pyo3(signature = (source, new_chunks, name=None, children=None))]
+    fn rechunk_from(
+        &mut self,
+        source: PyRef<'_, OmFilePyReader>,
+        new_chunks: Vec<u64>,
+        name: Option<&str>,
+        children: Option<Vec<OmVariable>>,
+    ) -> PyResult<OmVariable> {
+        let py = source.py();
+        let (compression, scale_factor, add_offset) = source.compression_parameters()?;
+        let source_name = source.variable_name()?;
+        let data = source.read_all(py)?;
+        self.write_array(
+            data.as_any(),
+            Some(new_chunks),
+            Some(scale_factor as f64),
+            Some(add_offset as f64),
+            Some(CompressionArg::Type(PyCompression(compression))),
+            Some(name.unwrap_or(&source_name)),
+            children,
+            None,
+            None,
+            None,
+            false,
+        )
+    }
+
+    /// Write a group without data which only holds its children. Like arrays, the last
+    /// written group becomes the root of the file if no array is written after it.
+    #[pyo3(signature = (name, children=None))]
+    fn write_group(
+        &mut self,
+        name: &str,
+        children: Option<Vec<OmVariable>>,
+    ) -> PyResult<OmVariable> {
+        let children = children
+            .unwrap_or_default()
+            .iter()
+            .map(OmVariable::offset_size)
+            .collect::<Vec<_>>();
+        let file_writer = self.file_writer.as_mut().ok_or_else(writer_closed)?;
+        let variable = file_writer
+            .write_none(name, &children)
+            .map_err(convert_omfilesrs_error)?;
+        let root = if self.root_children.is_empty() {
+            variable
+        } else {
+            file_writer
+                .write_none(
+                    name,
+                    &[children.as_slice(), self.root_children.as_slice()].concat(),
+                )
+                .map_err(convert_omfilesrs_error)?
+        };
+        self.write_root(variable, root)?;
+        Ok(OmVariable::new(name, &variable))
+    }
+
+    /// Write a numeric time coordinate with CF `units` and `calendar` attributes
+    /// stored as string scalar children.
+    #[pyo3(
+            text_signature = "(data, chunks, units, /, *, calendar='standard', name='time', children=None)",
+            signature = (data, chunks, units, calendar=None, name=None, children=None)
+        )]
+    fn write_time_array(
+        &mut self,
+        data: &Bound<'_, PyUntypedArray>,
+        chunks: Vec<u64>,
+        units: &str,
+        calendar: Option<&str>,
+        name: Option<&str>,
+        children: Option<Vec<OmVariable>>,
+    ) -> PyResult<OmVariable> {
+        let calendar = calendar.unwrap_or("standard");
+        if !SUPPORTED_CALENDARS.contains(&calendar) {
+            return Err(PyValueError::new_err(format!(
+                "Unsupported calendar: {}. Supported calendars are: {}",
+                calendar,
+                SUPPORTED_CALENDARS.join(", ")
+            )));
+        }
+        let py = data.py();
+
+        let mut children = children.unwrap_or_default();
+        children.push(self.write_scalar(units.into_pyobject(py)?.as_any(), "units", None)?);
+        children.push(self.write_scalar(calendar.into_pyobject(py)?.as_any(), "calendar", None)?);
+
+        self.write_array(
+            data.as_any(),
+            Some(chunks),
+            None,
+            None,
+            None,
+            Some(name.unwrap_or("time")),
+            Some(children),
+            None,
+            None,
+            None,
+            false,
+        )
+    }
+}
+
+/// Converts array-likes such as pandas or polars Series and Arrow arrays to a numpy array.
+/// A DataFrame is converted to a 2-D array with shape (rows, columns).
+fn as_numpy_array<'py>(data: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyUntypedArray>> {
+    if let Ok(array) = data.downcast::<PyUntypedArray>() {
+        return Ok(array.clone());
+    }
+    let array = data
+        .py()
+        .import("numpy")?
+        .call_method1("asarray", (data,))?;
+    Ok(array.downcast_into::<PyUntypedArray>()?)
 }
 
 impl OmFilePyWriter {
+    fn with_backend(
+        backend: WriterBackend,
+        file_path: String,
+        target_path: Option<String>,
+        buffer: Option<Arc<Mutex<Vec<u8>>>>,
+        metadata: Option<HashMap<String, Bound<'_, PyAny>>>,
+    ) -> PyResult<Self> {
+        let writer = OmFileWriter::new(backend, 8 * 1024); // initial capacity of 8KB
+        let mut py_writer = Self {
+            file_writer: Some(writer),
+            file_path,
+            buffer,
+            target_path,
+            root_children: Vec::new(),
+            root_records: HashMap::new(),
+            has_root: false,
+        };
+
+        if let Some(metadata) = metadata {
+            // Sort the keys so the written layout does not depend on dict ordering
+            let mut keys = metadata.keys().collect::<Vec<_>>();
+            keys.sort();
+            for key in keys {
+                if !FILE_METADATA_KEYS.contains(&key.as_str()) {
+                    return Err(PyValueError::new_err(format!(
+                        "Unsupported file metadata key: {}. Supported keys are: {}",
+                        key,
+                        FILE_METADATA_KEYS.join(", ")
+                    )));
+                }
+                let variable = py_writer.write_scalar_internal(&metadata[key], key, &[])?;
+                py_writer.root_children.push(variable);
+            }
+        }
+
+        Ok(py_writer)
+    }
+
+    fn memory_buffer(&self) -> PyResult<&Arc<Mutex<Vec<u8>>>> {
+        self.buffer.as_ref().ok_or_else(|| {
+            PyValueError::new_err("Only writers created with OmFilePyWriter.in_memory hold bytes")
+        })
+    }
+
+    /// Write a python scalar (str, int or float) as a scalar variable.
+    fn write_scalar_internal(
+        &mut self,
+        value: &Bound<'_, PyAny>,
+        name: &str,
+        children: &[OmOffsetSize],
+    ) -> PyResult<OmOffsetSize> {
+        let writer = self.file_writer.as_mut().ok_or_else(writer_closed)?;
+        let result = if let Ok(value) = value.extract::<String>() {
+            writer.write_scalar(value, name, children)
+        } else if let Ok(value) = value.extract::<i64>() {
+            writer.write_scalar(value, name, children)
+        } else if let Ok(value) = value.extract::<f64>() {
+            writer.write_scalar(value, name, children)
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "Unsupported scalar type for {}: {}",
+                name,
+                value.get_type().name()?
+            )));
+        };
+        result.map_err(convert_omfilesrs_error)
+    }
+
+    /// Reads a written array back and compares it with the input within the
+    /// quantization tolerance of the compression. Arrays with more than
+    /// `VERIFY_FULL_LIMIT` elements are only compared in their first chunk.
+    fn verify_array(
+        &self,
+        data: &Bound<'_, PyUntypedArray>,
+        variable: &OmVariable,
+        chunks: &[u64],
+        scale_factor: f32,
+        compression: &PyCompressionType,
+    ) -> PyResult<()> {
+        let py = data.py();
+        let verify_all = data.len() <= VERIFY_FULL_LIMIT;
+        let read_ranges = data
+            .shape()
+            .iter()
+            .zip(chunks)
+            .map(|(&dim, &chunk)| {
+                let dim = dim as u64;
+                0..if verify_all { dim } else { dim.min(chunk) }
+            })
+            .collect::<Vec<_>>();
+        let slices = read_ranges
+            .iter()
+            .map(|range| PySlice::new(py, range.start as isize, range.end as isize, 1))
+            .collect::<Vec<_>>();
+        let expected = data.get_item(PyTuple::new(py, slices)?)?;
+        let decoded = match &self.buffer {
+            Some(buffer) => {
+                let data = buffer.lock().unwrap().clone();
+                OmFilePyReader::read_variable_from_bytes(
+                    py,
+                    data,
+                    variable.offset_size(),
+                    read_ranges,
+                )?
+            }
+            None => OmFilePyReader::read_variable(
+                py,
+                &self.file_path,
+                variable.offset_size(),
+                read_ranges,
+            )?,
+        };
+
+        let numpy = py.import("numpy")?;
+        let tolerance = 1.0 / scale_factor as f64;
+        let is_float = data.dtype().kind() == b'f';
+        let matches = match compression {
+            PyCompressionType::PforDelta2dInt16 | PyCompressionType::PforDelta2d if is_float => {
+                let kwargs = [("rtol", 0.0), ("atol", tolerance)].into_py_dict(py)?;
+                kwargs.set_item("equal_nan", true)?;
+                numpy.call_method("allclose", (decoded, expected), Some(&kwargs))?
+            }
+            PyCompressionType::PforDelta2dInt16Logarithmic if is_float => {
+                // Values are quantized as log10(1 + x)
+                let decoded = numpy.call_method1("log1p", (decoded,))?;
+                let expected = numpy.call_method1("log1p", (expected,))?;
+                let kwargs = [("rtol", 0.0), ("atol", tolerance * std::f64::consts::LN_10)]
+                    .into_py_dict(py)?;
+                kwargs.set_item("equal_nan", true)?;
+                numpy.call_method("allclose", (decoded, expected), Some(&kwargs))?
+            }
+            _ => {
+                let kwargs = [("equal_nan", is_float)].into_py_dict(py)?;
+                numpy.call_method("array_equal", (decoded, expected), Some(&kwargs))?
+            }
+        };
+        if !matches.extract::<bool>()? {
+            return Err(PyRuntimeError::new_err(format!(
+                "Verification of variable {} failed: the data read back differs from the input",
+                variable.name
+            )));
+        }
+        Ok(())
+    }
+
     fn write_array_internal<'py, T>(
         &mut self,
         data: PyReadonlyArrayDyn<'py, T>,
@@ -134,7 +870,8 @@ impl OmFilePyWriter {
         add_offset: f32,
         compression: CompressionType,
         name: &str,
-    ) -> PyResult<()>
+        children: &[OmOffsetSize],
+    ) -> PyResult<OmVariable>
     where
         T: Element + OmFileArrayDataType,
     {
@@ -143,29 +880,316 @@ impl OmFilePyWriter {
             .into_iter()
             .map(|x| *x as u64)
             .collect::<Vec<u64>>();
+        validate_chunks(&dimensions, &chunks)?;
 
-        let mut writer = self
-            .file_writer
-            .prepare_array::<T>(dimensions, chunks, compression, scale_factor, add_offset)
+        let file_writer = self.file_writer.as_mut().ok_or_else(writer_closed)?;
+        let mut writer = file_writer
+            .prepare_array::<T>(
+                dimensions,
+                chunks.clone(),
+                compression,
+                scale_factor,
+                add_offset,
+            )
             .map_err(convert_omfilesrs_error)?;
 
+        // Transposed or strided views are copied to C order, the encoder needs contiguous data
+        let array = data.as_array();
+        let array = array.as_standard_layout();
         writer
-            .write_data(data.as_array(), None, None)
+            .write_data(array.view(), None, None)
             .map_err(convert_omfilesrs_error)?;
 
         let variable_meta = writer.finalize();
-        let variable = self
-            .file_writer
-            .write_array(variable_meta, name, &[])
+        // Every array is written as the root of the file until another array is written
+        let (variable, root) = self.write_array_records(variable_meta, name, children)?;
+        self.write_root(variable, root)?;
+
+        Ok(OmVariable::new(name, &variable).with_chunks(chunks))
+    }
+
+    fn write_chunks_internal<T>(
+        &mut self,
+        chunk_iter: &Bound<'_, PyAny>,
+        layout: ArrayLayout,
+        name: &str,
+        children: &[OmOffsetSize],
+    ) -> PyResult<OmVariable>
+    where
+        T: Element + OmFileArrayDataType,
+    {
+        let chunk_counts = layout.chunk_counts();
+        let total_chunks = chunk_counts.iter().product::<u64>();
+        let mut expected = vec![0u64; layout.dimensions.len()];
+        let mut written = 0u64;
+
+        let file_writer = self.file_writer.as_mut().ok_or_else(writer_closed)?;
+        let mut writer = file_writer
+            .prepare_array::<T>(
+                layout.dimensions.clone(),
+                layout.chunks.clone(),
+                layout.compression,
+                layout.scale_factor,
+                layout.add_offset,
+            )
             .map_err(convert_omfilesrs_error)?;
-        self.file_writer
-            .write_trailer(variable)
+
+        for item in chunk_iter.try_iter()? {
+            let (coords, data) = item?.extract::<(Vec<u64>, Bound<'_, PyAny>)>()?;
+            if written == total_chunks {
+                return Err(PyValueError::new_err(format!(
+                    "Got more than the {} chunks of the array",
+                    total_chunks
+                )));
+            }
+            if coords != expected {
+                return Err(PyValueError::new_err(format!(
+                    "Expected chunk {:?}, got {:?}. Chunks have to be written in row-major order",
+                    expected, coords
+                )));
+            }
+            let mut data = as_numpy_array(&data)?;
+            // float16 chunks are stored as float32
+            if data
+                .dtype()
+                .is_equiv_to(&PyArrayDescr::new(data.py(), "float16")?)
+            {
+                data = data
+                    .call_method1("astype", ("float32",))?
+                    .downcast_into::<PyUntypedArray>()?;
+            }
+            if matches!(
+                layout.compression,
+                CompressionType::PforDelta2dInt16Logarithmic
+            ) {
+                check_non_negative(&data)?;
+            }
+            let data = data
+                .downcast::<PyArrayDyn<T>>()
+                .map_err(|_| {
+                    PyTypeError::new_err(format!(
+                        "Chunk {:?} has dtype {}, expected {}",
+                        coords,
+                        data.dtype(),
+                        dtype::<T>(data.py())
+                    ))
+                })?
+                .readonly();
+            let shape = data.shape().iter().map(|&x| x as u64).collect::<Vec<_>>();
+            let expected_shape = layout.chunk_shape(&coords);
+            if shape != expected_shape {
+                return Err(PyValueError::new_err(format!(
+                    "Chunk {:?} has shape {:?}, expected {:?}",
+                    coords, shape, expected_shape
+                )));
+            }
+
+            let array = data.as_array();
+            let array = array.as_standard_layout();
+            writer
+                .write_data(array.view(), None, None)
+                .map_err(convert_omfilesrs_error)?;
+            written += 1;
+            next_chunk_coords(&mut expected, &chunk_counts);
+        }
+        if written != total_chunks {
+            return Err(PyValueError::new_err(format!(
+                "Got {} of the {} chunks of the array",
+                written, total_chunks
+            )));
+        }
+
+        let variable_meta = writer.finalize();
+        let (variable, root) = self.write_array_records(variable_meta, name, children)?;
+        self.write_root(variable, root)?;
+
+        Ok(OmVariable::new(name, &variable).with_chunks(layout.chunks))
+    }
+
+    /// Writes the metadata record of a finalized array. With file metadata, a second record
+    /// of the same data is written which additionally has the metadata scalars as children.
+    /// Returns the record of the variable and the record to use if it becomes the root.
+    fn write_array_records(
+        &mut self,
+        variable_meta: OmFileWriterArrayFinalized,
+        name: &str,
+        children: &[OmOffsetSize],
+    ) -> PyResult<(OmOffsetSize, OmOffsetSize)> {
+        let file_writer = self.file_writer.as_mut().ok_or_else(writer_closed)?;
+        if self.root_children.is_empty() {
+            let variable = file_writer
+                .write_array(variable_meta, name, children)
+                .map_err(convert_omfilesrs_error)?;
+            return Ok((variable, variable));
+        }
+        let variable = file_writer
+            .write_array(variable_meta.clone(), name, children)
+            .map_err(convert_omfilesrs_error)?;
+        let root = file_writer
+            .write_array(
+                variable_meta,
+                name,
+                &[children, self.root_children.as_slice()].concat(),
+            )
             .map_err(convert_omfilesrs_error)?;
+        Ok((variable, root))
+    }
 
+    /// Writes a trailer pointing to `root`, the record of `variable` with the file metadata.
+    /// `set_root` looks the record up to attach the metadata when it is called with `variable`.
+    fn write_root(&mut self, variable: OmOffsetSize, root: OmOffsetSize) -> PyResult<()> {
+        let file_writer = self.file_writer.as_mut().ok_or_else(writer_closed)?;
+        file_writer
+            .write_trailer(root)
+            .map_err(convert_omfilesrs_error)?;
+        if !self.root_children.is_empty() {
+            self.root_records
+                .insert((variable.offset, variable.size), root);
+        }
+        self.has_root = true;
         Ok(())
     }
 }
 
+/// Shape and encoding of an array written with `write_array_from_iter`.
+struct ArrayLayout {
+    dimensions: Vec<u64>,
+    chunks: Vec<u64>,
+    scale_factor: f32,
+    add_offset: f32,
+    compression: CompressionType,
+}
+
+impl ArrayLayout {
+    /// Number of chunks along each dimension.
+    fn chunk_counts(&self) -> Vec<u64> {
+        self.dimensions
+            .iter()
+            .zip(&self.chunks)
+            .map(|(&dim, &chunk)| dim.div_ceil(chunk.max(1)))
+            .collect()
+    }
+
+    /// Shape of the chunk at `coords`, which is cut off at the upper array boundaries.
+    fn chunk_shape(&self, coords: &[u64]) -> Vec<u64> {
+        self.dimensions
+            .iter()
+            .zip(&self.chunks)
+            .zip(coords)
+            .map(|((&dim, &chunk), &coord)| chunk.min(dim - coord * chunk))
+            .collect()
+    }
+}
+
+/// Advances `coords` to the next chunk in row-major order. Returns false after the last chunk.
+fn next_chunk_coords(coords: &mut [u64], chunk_counts: &[u64]) -> bool {
+    for (coord, &count) in coords.iter_mut().zip(chunk_counts).rev() {
+        *coord += 1;
+        if *coord < count {
+            return true;
+        }
+        *coord = 0;
+    }
+    false
+}
+
+/// Checks that there is one chunk dimension per array dimension and that each is in
+/// `1..=dimension`. Larger chunks would only waste space. Empty dimensions use chunks of 1.
+fn validate_chunks(dimensions: &[u64], chunks: &[u64]) -> PyResult<()> {
+    if dimensions.len() != chunks.len() {
+        return Err(PyValueError::new_err(format!(
+            "Got {} chunk dimensions for an array with {} dimensions",
+            chunks.len(),
+            dimensions.len()
+        )));
+    }
+    for (axis, (&dim, &chunk)) in dimensions.iter().zip(chunks).enumerate() {
+        if chunk == 0 || chunk > dim.max(1) {
+            return Err(PyValueError::new_err(format!(
+                "Chunk dimension {} of axis {} must be between 1 and the dimension {}",
+                chunk,
+                axis,
+                dim.max(1)
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Parses the compression arguments shared by `write_array` and `write_array_from_iter`.
+/// Scale factor and offset are returned as the 32 bit floats stored in the file.
+fn compression_arguments(
+    compression: Option<CompressionArg>,
+    scale_factor: Option<f64>,
+    add_offset: Option<f64>,
+    is_float64: bool,
+) -> PyResult<(PyCompressionType, f32, f32)> {
+    let compression = compression
+        .map(CompressionArg::resolve)
+        .transpose()?
+        .unwrap_or(PyCompressionType::PforDelta2d);
+    let scale_factor = scale_factor.unwrap_or(1.0);
+    let add_offset = add_offset.unwrap_or(0.0);
+    if is_float64 && compression.is_quantized() {
+        check_offset_precision(scale_factor, add_offset)?;
+    }
+    Ok((compression, scale_factor as f32, add_offset as f32))
+}
+
+/// Rejects offsets of quantized float64 arrays which change by more than half a
+/// quantization step when stored as the 32 bit float of the file format, as this would
+/// shift all decoded values. E.g. an offset of 1.7e9 epoch seconds is off by up to 64.
+fn check_offset_precision(scale_factor: f64, add_offset: f64) -> PyResult<()> {
+    let error = (add_offset - add_offset as f32 as f64).abs();
+    let half_step = 0.5 / scale_factor.abs();
+    if error > half_step {
+        return Err(PyValueError::new_err(format!(
+            "add_offset {} is stored as a 32 bit float with an error of {}, which exceeds half the \
+             quantization step of scale_factor {}. Subtract the offset from the data before writing instead",
+            add_offset, error, scale_factor
+        )));
+    }
+    Ok(())
+}
+
+/// Rejects negative values for the logarithmic compression, which stores `log10(1 + x)`
+/// and would decode them to NaN. NaN values in the input are allowed.
+fn check_non_negative(data: &Bound<'_, PyUntypedArray>) -> PyResult<()> {
+    let numpy = data.py().import("numpy")?;
+    let negative = numpy.call_method1("less", (data, 0))?;
+    if !negative.call_method0("any")?.is_truthy()? {
+        return Ok(());
+    }
+    let minimum = numpy.call_method1("nanmin", (data,))?;
+    Err(PyValueError::new_err(format!(
+        "Logarithmic compression requires non-negative values, e.g. precipitation, \
+         but the data contains {}",
+        minimum
+    )))
+}
+
+fn writer_closed() -> PyErr {
+    PyValueError::new_err("Cannot write to a closed OmFilePyWriter")
+}
+
+/// Chunks used by `write_array` if none are given. The last dimension is kept
+/// contiguous as far as possible and leading dimensions are chunked with the
+/// remaining budget of `DEFAULT_CHUNK_ELEMENTS`.
+fn default_chunks(shape: &[usize]) -> Vec<u64> {
+    let mut budget = DEFAULT_CHUNK_ELEMENTS;
+    let mut chunks = shape
+        .iter()
+        .rev()
+        .map(|&dim| {
+            let chunk = (dim as u64).clamp(1, budget);
+            budget = (budget / chunk).max(1);
+            chunk
+        })
+        .collect::<Vec<_>>();
+    chunks.reverse();
+    chunks
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,11 +1210,22 @@ mod tests {
             let data = ArrayD::from_shape_fn(dimensions, |idx| (idx[0] + idx[1]) as f32);
             let py_array = PyArrayDyn::from_array(py, &data);
 
-            let mut file_writer = OmFilePyWriter::new(file_path).unwrap();
+            let mut file_writer = OmFilePyWriter::new(file_path, None, false).unwrap();
 
             // Write data
-            let result =
-                file_writer.write_array(py_array.as_untyped(), chunks, None, None, None, None);
+            let result = file_writer.write_array(
+                py_array.as_any(),
+                Some(chunks),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+            );
 
             assert!(result.is_ok());
             assert!(fs::metadata(file_path).is_ok());
@@ -201,4 +1236,180 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_default_chunks() {
+        assert_eq!(default_chunks(&[100]), vec![100]);
+        assert_eq!(default_chunks(&[1_000_000]), vec![DEFAULT_CHUNK_ELEMENTS]);
+        assert_eq!(default_chunks(&[720, 1440]), vec![45, 1440]);
+        assert_eq!(default_chunks(&[24, 721, 1440]), vec![1, 45, 1440]);
+        assert_eq!(default_chunks(&[0, 10]), vec![1, 10]);
+    }
+
+    #[test]
+    fn test_check_offset_precision() {
+        assert!(check_offset_precision(1.0, 0.0).is_ok());
+        assert!(check_offset_precision(100.0, 0.1).is_ok());
+        assert!(check_offset_precision(1.0, 1_700_000_000.0).is_ok());
+        assert!(check_offset_precision(1.0, 1_700_000_001.0).is_err());
+        assert!(check_offset_precision(0.001, 1_700_000_001.0).is_ok());
+    }
+
+    #[test]
+    fn test_validate_chunks() {
+        assert!(validate_chunks(&[10, 20], &[5, 20]).is_ok());
+        assert!(validate_chunks(&[0, 3], &[1, 3]).is_ok());
+        assert!(validate_chunks(&[10, 20], &[5]).is_err());
+        assert!(validate_chunks(&[10, 20], &[0, 20]).is_err());
+        assert!(validate_chunks(&[10, 20], &[11, 20]).is_err());
+    }
+
+    #[test]
+    fn test_chunk_iteration() {
+        let layout = ArrayLayout {
+            dimensions: vec![5, 3],
+            chunks: vec![2, 2],
+            scale_factor: 1.0,
+            add_offset: 0.0,
+            compression: CompressionType::None,
+        };
+        assert_eq!(layout.chunk_counts(), vec![3, 2]);
+        assert_eq!(layout.chunk_shape(&[0, 0]), vec![2, 2]);
+        assert_eq!(layout.chunk_shape(&[2, 1]), vec![1, 1]);
+
+        let mut coords = vec![0, 0];
+        let mut visited = vec![coords.clone()];
+        while next_chunk_coords(&mut coords, &layout.chunk_counts()) {
+            visited.push(coords.clone());
+        }
+        assert_eq!(
+            visited,
+            vec![
+                vec![0, 0],
+                vec![0, 1],
+                vec![1, 0],
+                vec![1, 1],
+                vec![2, 0],
+                vec![2, 1]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_supported_dtypes_are_writable() -> Result<(), Box<dyn std::error::Error>> {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| -> Result<(), Box<dyn std::error::Error>> {
+            let file_path = "test_supported_dtypes.om";
+            let numpy = py.import("numpy")?;
+
+            for dtype in supported_dtypes() {
+                let array = numpy
+                    .call_method1("zeros", ((5, 5), dtype))?
+                    .downcast_into::<PyUntypedArray>()
+                    .map_err(PyErr::from)?;
+                let mut file_writer = OmFilePyWriter::new(file_path, None, false)?;
+                file_writer.write_array(
+                    array.as_any(),
+                    Some(vec![5, 5]),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    false,
+                )?;
+            }
+
+            fs::remove_file(file_path)?;
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_uncompressed_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| -> Result<(), Box<dyn std::error::Error>> {
+            let file_path = "test_write_uncompressed.om";
+            let data = ArrayD::from_shape_fn(vec![6, 4], |idx| {
+                (idx[0] as i32 - 3) * 1_000_000 + idx[1] as i32
+            });
+            let py_array = PyArrayDyn::from_array(py, &data);
+
+            let mut file_writer = OmFilePyWriter::new(file_path, None, false)?;
+            let variable = file_writer.write_array(
+                py_array.as_any(),
+                Some(vec![4, 4]),
+                None,
+                None,
+                Some(CompressionArg::Name("none".to_string())),
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+            )?;
+            drop(file_writer);
+
+            let read_ranges = vec![0..6, 0..4];
+            let read =
+                OmFilePyReader::read_variable(py, file_path, variable.offset_size(), read_ranges)?
+                    .downcast_into::<PyArrayDyn<i32>>()
+                    .map_err(PyErr::from)?;
+            assert_eq!(read.readonly().as_array(), data.view());
+
+            fs::remove_file(file_path)?;
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_in_memory_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| -> Result<(), Box<dyn std::error::Error>> {
+            let data = ArrayD::from_shape_fn(vec![5, 3], |idx| (idx[0] * 3 + idx[1]) as f32);
+            let py_array = PyArrayDyn::from_array(py, &data);
+
+            let mut writer = OmFilePyWriter::in_memory(None)?;
+            let variable = writer.write_array(
+                py_array.as_any(),
+                Some(vec![2, 2]),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+            )?;
+            let bytes = writer.to_bytes(py)?.as_bytes().to_vec();
+            assert_eq!(&bytes[0..2], b"OM");
+
+            let read = OmFilePyReader::read_variable_from_bytes(
+                py,
+                bytes,
+                variable.offset_size(),
+                vec![0..5, 0..3],
+            )?
+            .downcast_into::<PyArrayDyn<f32>>()
+            .map_err(PyErr::from)?;
+            assert_eq!(read.readonly().as_array(), data.view());
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
 }
@@ -1,8 +1,12 @@
+import io
+import json
 import os
+import sys
 
 import fsspec
 import numpy as np
 import omfilesrspy
+import pytest
 
 from .test_utils import create_test_om_file
 
@@ -75,6 +79,2002 @@ def test_round_trip_array_datatypes():
             os.remove(temp_file)
 
 
+def test_file_metadata():
+    temp_file = "test_file_metadata.om"
+    metadata = {"_created_by": "omfilesrspy", "_created_at": 1735689600, "_om_version": 3}
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file, metadata=metadata)
+        units = writer.write_scalar("K", name="units")
+        child = writer.write_array(np.zeros(5, dtype=np.float32), chunks=[5], name="child", children=[units])
+        writer.write_array(np.zeros((5, 5), dtype=np.float32), chunks=[5, 5], children=[child])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.file_metadata() == metadata
+        assert reader.attrs == {}
+        child_reader = reader.get_child_by_name("child")
+        assert child_reader.file_metadata() == {}
+        assert child_reader.attrs == {"units": "K"}
+        del child_reader
+        del reader
+
+        with omfilesrspy.OmFilePyWriter(temp_file, metadata=metadata) as writer:
+            child = writer.write_array(np.zeros(5, dtype=np.float32), chunks=[5], name="child")
+            writer.write_array(np.zeros((5, 5), dtype=np.float32), chunks=[5, 5], children=[child])
+            writer.set_root(child)
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.variable_name() == "child"
+        assert reader.file_metadata() == metadata
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_file_metadata_invalid_key():
+    temp_file = "test_file_metadata_invalid.om"
+
+    try:
+        with pytest.raises(ValueError):
+            omfilesrspy.OmFilePyWriter(temp_file, metadata={"author": "me"})
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_saturation_policy():
+    temp_file = "test_saturation.om"
+
+    try:
+        for dtype in [np.float32, np.float64]:
+            data = np.zeros((5, 5), dtype=dtype)
+            data[0, 0] = -40000.0
+            data[4, 4] = 40000.0
+
+            writer = omfilesrspy.OmFilePyWriter(temp_file)
+            writer.write_array(data, chunks=[5, 5], scale_factor=1.0, compression="pfor_delta_2d_int16")
+            del writer
+
+            reader = omfilesrspy.OmFilePyReader(temp_file)
+            kept = reader.read((slice(None), slice(None)), saturation_policy="keep")
+            masked = reader.read((slice(None), slice(None)), saturation_policy="nan")
+            with pytest.raises(ValueError):
+                reader.read((slice(None), slice(None)), saturation_policy="clip")
+            del reader
+
+            assert masked.dtype == dtype
+            assert not np.isnan(kept[0, 0])
+            assert kept[0, 0] < -30000.0
+            assert np.isnan(masked[0, 0])
+            assert np.isnan(masked[4, 4])
+            np.testing.assert_array_equal(masked[1:4, 1:4], data[1:4, 1:4])
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_saturation_policy_float16():
+    temp_file = "test_saturation_float16.om"
+    data = np.zeros((4, 4), dtype=np.float16)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(data, chunks=[4, 4], compression="pfor_delta_2d_int16")
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        with pytest.raises(ValueError, match="not supported"):
+            reader.read((slice(None), slice(None)), saturation_policy="nan")
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_masked():
+    temp_file = "test_read_masked.om"
+    # Only the interior is written, the border stays NaN like after a write at an offset
+    data = np.full((6, 6), np.nan, dtype=np.float32)
+    data[1:5, 1:5] = np.arange(16, dtype=np.float32).reshape(4, 4)
+    int_data = np.arange(36, dtype=np.int32).reshape(6, 6)
+    int_data[0, :] = -1
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        float_var = writer.write_array(data, chunks=[4, 4], name="float")
+        int_var = writer.write_array(int_data, chunks=[4, 4], name="int")
+        writer.write_group("root", children=[float_var, int_var])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        masked = reader["float"].read_masked((slice(None), slice(None)))
+        assert isinstance(masked, np.ma.MaskedArray)
+        np.testing.assert_array_equal(masked.mask, np.isnan(data))
+        np.testing.assert_array_equal(masked.compressed(), data[1:5, 1:5].ravel())
+        assert masked.mean() == data[1:5, 1:5].mean()
+
+        int_masked = reader["int"].read_masked((slice(None), slice(None)), fill_value=-1)
+        np.testing.assert_array_equal(int_masked.mask, int_data == -1)
+        assert not reader["int"].read_masked((slice(None), slice(None))).mask.any()
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_logarithmic_compression():
+    temp_file = "test_logarithmic_compression.om"
+    precipitation = np.random.default_rng(1).gamma(0.5, 4.0, size=(6, 8)).astype(np.float32)
+    precipitation[0, :] = 0.0
+    precipitation[1, 1] = np.nan
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(
+            precipitation, chunks=[3, 4], scale_factor=1000.0, compression="pfor_delta_2d_int16_logarithmic"
+        )
+        with pytest.raises(ValueError, match="non-negative"):
+            writer.write_array(-precipitation, chunks=[3, 4], compression="pfor_delta_2d_int16_logarithmic")
+        with pytest.raises(ValueError, match="non-negative"):
+            writer.write_array_from_iter(
+                iter([([0], np.array([1.0, -0.5], dtype=np.float32))]),
+                dimensions=[2],
+                chunks=[2],
+                dtype="float32",
+                compression="pfor_delta_2d_int16_logarithmic",
+            )
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        np.testing.assert_allclose(reader[:, :], precipitation, rtol=0.01, atol=0.001)
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_supported_types():
+    assert omfilesrspy.supported_compressions() == [
+        "pfor_delta_2d_int16",
+        "fpx_xor_2d",
+        "pfor_delta_2d",
+        "pfor_delta_2d_int16_logarithmic",
+        "none",
+    ]
+    assert "float32" in omfilesrspy.supported_dtypes()
+    assert "uint16" in omfilesrspy.supported_dtypes()
+
+
+def test_read_into_with_cast():
+    temp_file = "test_read_into_cast.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, dtype=np.float64)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        out = np.zeros((5, 5), dtype=np.float32)
+        with pytest.warns(RuntimeWarning):
+            reader.read_into(out, (slice(None), slice(None)))
+        np.testing.assert_array_equal(out, test_data.astype(np.float32))
+
+        with pytest.raises(ValueError):
+            reader.read_into(np.zeros((2, 2), dtype=np.float64), (slice(None), slice(None)))
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_from_path_validate():
+    temp_file = "test_validate.om"
+
+    try:
+        create_test_om_file(temp_file)
+
+        reader = omfilesrspy.OmFilePyReader.from_path(temp_file, validate=True)
+        assert reader.validate() == []
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_view_dtype():
+    temp_file = "test_view_dtype.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        bits = reader.read((slice(None), slice(None)), view_dtype="uint32")
+        assert bits.dtype == np.uint32
+        np.testing.assert_array_equal(bits, test_data.view(np.uint32))
+
+        with pytest.raises(ValueError):
+            reader.read((slice(None), slice(None)), view_dtype="uint64")
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_variable_equality():
+    temp_file = "test_variable_equality.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        first = writer.write_scalar(1, name="first")
+        second = writer.write_scalar(2, name="second")
+        root = writer.write_group("root", children=list({first, second, first}))
+        del writer
+
+        assert first == first
+        assert first != second
+        assert first != "first"
+        assert len({first, second, first}) == 2
+        offsets = {first: first.offset, second: second.offset}
+        assert offsets[first] == first.offset
+        assert root not in offsets
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_variable_paths():
+    temp_file = "test_variable_paths.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file, metadata={"_created_by": "test"})
+        writer.write_array(np.zeros((5, 5), dtype=np.float32), chunks=[5, 5], name="temperature")
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        paths = reader.variable_paths()
+        assert paths == sorted(reader.get_flat_variable_metadata().keys())
+        assert "temperature" in paths
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_time_coordinate_calendar():
+    temp_file = "test_time_calendar.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_time_array(
+            np.array([0, 59, 365], dtype=np.int64), chunks=[3], units="days since 2001-01-01", calendar="noleap"
+        )
+        with pytest.raises(ValueError):
+            writer.write_time_array(np.zeros(3, dtype=np.int64), chunks=[3], units="days since 2001-01-01", calendar="mars")
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        times = reader.decode_time()
+        del reader
+
+        assert [(t.year, t.month, t.day) for t in times] == [(2001, 1, 1), (2001, 3, 1), (2002, 1, 1)]
+        assert times[0].calendar == "noleap"
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_points():
+    temp_file = "test_read_points.om"
+    test_data = np.arange(200, dtype=np.float32).reshape(10, 20)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(test_data, chunks=[3, 7])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        points = [(9, 19), (0, 0), (1, 2), (5, 14), (-1, 0), (2, 1)]
+        values = reader.read_points(points)
+
+        assert values.shape == (len(points),)
+        np.testing.assert_array_equal(values, [test_data[p] for p in points])
+
+        with pytest.raises(IndexError):
+            reader.read_points([(10, 0)])
+        with pytest.raises(IndexError):
+            reader.read_points([(1,)])
+
+        reader.set_io_hints(io_size_max=64, io_size_merge=0)
+        np.testing.assert_array_equal(reader.read_points(points), values)
+        del reader
+
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(test_data.astype(np.float16), chunks=[3, 7])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        values = reader.read_points(points)
+        assert values.dtype == np.float16
+        np.testing.assert_array_equal(values, [reader[p] for p in points])
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_into_nan_replacement():
+    temp_file = "test_read_into_nan.om"
+    test_data = np.arange(25, dtype=np.float32).reshape(5, 5)
+    test_data[2, 3] = np.nan
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(test_data, chunks=[5, 5])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        out = np.zeros((5, 5), dtype=np.int32)
+        with pytest.raises(ValueError):
+            reader.read_into(out, (slice(None), slice(None)))
+
+        with pytest.warns(RuntimeWarning):
+            reader.read_into(out, (slice(None), slice(None)), nan_replacement=-1)
+        del reader
+
+        assert out[2, 3] == -1
+        assert out[4, 4] == 24
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_chunk_grid():
+    temp_file = "test_chunk_grid.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(np.zeros((10, 20, 3), dtype=np.float32), chunks=[3, 20, 2])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.chunk_grid() == (4, 1, 2)
+        assert reader.chunk_count() == 8
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_pandas_series():
+    pd = pytest.importorskip("pandas")
+    temp_file = "test_write_pandas.om"
+    series = pd.Series(np.arange(10, dtype=np.int32))
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(series, chunks=[5])
+        with pytest.raises(ValueError):
+            writer.write_array(pd.Series(["a", "b"]), chunks=[2])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        np.testing.assert_array_equal(reader[:], series.to_numpy())
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_reader_without_squeeze():
+    temp_file = "test_no_squeeze.om"
+
+    try:
+        create_test_om_file(temp_file)
+
+        reader = omfilesrspy.OmFilePyReader(temp_file, squeeze=False)
+        assert reader[1, 0:5].shape == (1, 5)
+        assert reader[1, 2].shape == (1, 1)
+        del reader
+
+        reader = omfilesrspy.OmFilePyReader.from_path(temp_file)
+        assert reader[1, 0:5].shape == (5,)
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_embedded_file():
+    temp_file = "test_embedded_source.om"
+    container_file = "test_container.bin"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        with open(temp_file, "rb") as f:
+            om_bytes = f.read()
+        with open(container_file, "wb") as f:
+            f.write(b"\x00" * 64 + om_bytes + b"footer")
+
+        reader = omfilesrspy.OmFilePyReader.from_path(container_file, base_offset=64, length=len(om_bytes))
+        np.testing.assert_array_equal(reader[:], test_data)
+        del reader
+
+        with pytest.raises(ValueError):
+            omfilesrspy.OmFilePyReader.from_path(container_file, base_offset=64, length=len(om_bytes) + 100)
+
+    finally:
+        os.remove(temp_file)
+        os.remove(container_file)
+
+
+def test_read_with_indices():
+    temp_file = "test_read_with_indices.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        data, indices = reader.read((slice(1, 4), slice(2, 5)), with_indices=True)
+        np.testing.assert_array_equal(data, test_data[1:4, 2:5])
+        assert len(indices) == 2
+        np.testing.assert_array_equal(indices[0], [1, 2, 3])
+        np.testing.assert_array_equal(indices[1], [2, 3, 4])
+
+        data, indices = reader.read((2, slice(0, 3)), with_indices=True)
+        assert data.shape == (3,)
+        assert len(indices) == 1
+        np.testing.assert_array_equal(indices[0], [0, 1, 2])
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_open_errors():
+    with pytest.raises(FileNotFoundError):
+        omfilesrspy.OmFilePyReader.from_path("does_not_exist.om")
+
+    empty_file = "test_open_errors_empty.om"
+    not_om_file = "test_open_errors_not_om.om"
+    truncated_file = "test_open_errors_truncated.om"
+    try:
+        open(empty_file, "wb").close()
+        with pytest.raises(omfilesrspy.OmNotAnOmFileError):
+            omfilesrspy.OmFilePyReader.from_path(empty_file)
+
+        with open(not_om_file, "wb") as f:
+            f.write(b"definitely not an om file")
+        with pytest.raises(omfilesrspy.OmNotAnOmFileError):
+            omfilesrspy.OmFilePyReader.from_path(not_om_file)
+
+        with open(truncated_file, "wb") as f:
+            f.write(b"OM\x03")
+        with pytest.raises(omfilesrspy.OmTruncatedFileError):
+            omfilesrspy.OmFilePyReader.from_path(truncated_file)
+
+    finally:
+        for path in (empty_file, not_om_file, truncated_file):
+            if os.path.exists(path):
+                os.remove(path)
+
+
+def test_release_prefetch():
+    temp_file = "test_release_prefetch.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        reader.release_prefetch()
+        np.testing.assert_array_equal(reader[0:5, 0:5], test_data)
+        assert reader.cache_stats() == {"hits": 0, "misses": 0, "entries": 0, "bytes": 0}
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_empty_slice():
+    temp_file = "test_read_empty_slice.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        data = reader[3:1, 0:5]
+        assert data.shape == test_data[3:1, 0:5].shape
+        assert data.dtype == np.float32
+
+        data = reader[2, 4:4]
+        assert data.shape == (0,)
+
+        with pytest.raises(NotImplementedError):
+            reader[3:1:-1, 0:5]
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_compression_property_matches_writer():
+    temp_file = "test_compression_property.om"
+
+    try:
+        for compression in omfilesrspy.supported_compressions():
+            writer = omfilesrspy.OmFilePyWriter(temp_file)
+            writer.write_array(
+                np.arange(16, dtype=np.float32).reshape(4, 4), chunks=[2, 2], scale_factor=10.0, compression=compression
+            )
+            del writer
+
+            reader = omfilesrspy.OmFilePyReader(temp_file)
+            assert str(reader.compression) == compression
+            assert reader.scale_factor == 10.0
+            assert reader.add_offset == 0.0
+            del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_compression_type():
+    temp_file = "test_compression_type.om"
+    CompressionType = omfilesrspy.CompressionType
+
+    members = [
+        CompressionType.PFOR_DELTA_2D_INT16,
+        CompressionType.FPX_XOR_2D,
+        CompressionType.PFOR_DELTA_2D,
+        CompressionType.PFOR_DELTA_2D_INT16_LOGARITHMIC,
+        CompressionType.NONE,
+    ]
+    assert [int(member) for member in members] == [0, 1, 2, 3, 4]
+    assert [str(member) for member in members] == omfilesrspy.supported_compressions()
+    assert str(CompressionType.FPX_XOR_2D) == "fpx_xor_2d"
+    assert repr(CompressionType.FPX_XOR_2D) == "CompressionType.FPX_XOR_2D"
+    assert CompressionType.FPX_XOR_2D == 1
+    assert CompressionType.FPX_XOR_2D != "fpx_xor_2d"
+    assert CompressionType.FPX_XOR_2D != CompressionType.NONE
+    assert CompressionType.FPX_XOR_2D.__eq__("fpx_xor_2d") is NotImplemented
+    assert len({CompressionType.NONE, CompressionType.NONE}) == 1
+
+    # Equal objects have to hash equally for mixed dicts and sets
+    for member in members:
+        assert hash(member) == hash(int(member))
+    assert {CompressionType.FPX_XOR_2D, 1, "fpx_xor_2d"} == {1, "fpx_xor_2d"}
+    lookup = {1: "by int", "fpx_xor_2d": "by name"}
+    assert lookup[CompressionType.FPX_XOR_2D] == "by int"
+    assert {CompressionType.NONE: "none"}[4] == "none"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        by_type = writer.write_array(
+            np.arange(16, dtype=np.float32).reshape(4, 4), chunks=[2, 2], compression=CompressionType.FPX_XOR_2D
+        )
+        by_name = writer.write_array(np.arange(4, dtype=np.float32), chunks=[4], compression="none")
+        writer.write_group("root", children=[by_type, by_name])
+        with pytest.raises(ValueError):
+            writer.write_array(np.zeros(4, dtype=np.float32), chunks=[4], compression="zstd")
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader["data"].compression == CompressionType.FPX_XOR_2D
+        assert reader.children[1].compression == CompressionType.NONE
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_compression_info():
+    temp_file = "test_compression_info.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        writer.write_array(
+            np.zeros((4, 4), dtype=np.float32),
+            chunks=[2, 2],
+            scale_factor=100.0,
+            add_offset=1.0,
+            compression="pfor_delta_2d_int16",
+            children=[units],
+        )
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        info = reader.compression_info()
+        assert info["compression"] == "pfor_delta_2d_int16"
+        assert info["scale_factor"] == 100.0
+        assert info["add_offset"] == 1.0
+        assert info["uniform"] is True
+        assert info["chunks"] is None
+
+        assert reader.compression == omfilesrspy.CompressionType.PFOR_DELTA_2D_INT16
+        assert reader.scale_factor == 100.0
+        assert reader.add_offset == 1.0
+        assert reader.chunk_dimensions == [2, 2]
+
+        with pytest.raises(ValueError):
+            reader["units"].compression_info()
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_with_attrs():
+    temp_file = "test_read_with_attrs.om"
+    test_data = np.arange(20, dtype=np.float32).reshape(4, 5)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        long_name = writer.write_scalar("Air temperature", name="long_name")
+        writer.write_array(test_data, chunks=[2, 5], name="temperature", children=[units, long_name])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        result = reader.read_with_attrs((slice(0, 2), slice(None)))
+        np.testing.assert_array_equal(result.array, test_data[0:2, :])
+        assert result.attrs == {"units": "K", "long_name": "Air temperature"}
+
+        array, attrs = result
+        assert array.shape == (2, 5)
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_categories():
+    temp_file = "test_categories.om"
+    test_data = np.array([[0, 1, 2], [2, 1, 5]], dtype=np.uint8)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(test_data, chunks=[2, 3], categories={0: "ocean", 1: "land", 2: "ice"})
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.categories() == {0: "ocean", 1: "land", 2: "ice"}
+        labels = reader.read((slice(None), slice(None)), decode_categories=True)
+        assert labels.dtype == object
+        assert labels.tolist() == [["ocean", "land", "ice"], ["ice", "land", None]]
+        del reader
+
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        with pytest.raises(ValueError):
+            writer.write_array(test_data.astype(np.float32), chunks=[2, 3], categories={0: "ocean"})
+        del writer
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_from_reader():
+    temp_file = "test_from_reader.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+
+        with open(temp_file, "rb") as f:
+            reader = omfilesrspy.OmFilePyReader.from_reader(f)
+            np.testing.assert_array_equal(reader[0:5, 0:5], test_data)
+            del reader
+
+        with open(temp_file, "rb") as f:
+            buffer = io.BytesIO(f.read())
+        reader = omfilesrspy.OmFilePyReader.from_reader(buffer, size=len(buffer.getvalue()))
+        np.testing.assert_array_equal(reader[1:3, 2:4], test_data[1:3, 2:4])
+        del reader
+
+        with pytest.raises(TypeError):
+            omfilesrspy.OmFilePyReader.from_reader(object())
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_from_bytes():
+    temp_file = "test_from_bytes.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        with open(temp_file, "rb") as f:
+            data = f.read()
+    finally:
+        os.remove(temp_file)
+
+    reader = omfilesrspy.OmFilePyReader.from_bytes(data)
+    np.testing.assert_array_equal(reader[0:5, 0:5], test_data)
+
+    buffer = bytearray(data)
+    reader = omfilesrspy.OmFilePyReader.from_bytes(buffer)
+    buffer[:] = bytes(len(buffer))
+    np.testing.assert_array_equal(reader[1:3, 2:4], test_data[1:3, 2:4])
+
+    with pytest.raises(omfilesrspy.OmNotAnOmFileError):
+        omfilesrspy.OmFilePyReader.from_bytes(b"")
+    with pytest.raises(TypeError):
+        omfilesrspy.OmFilePyReader.from_bytes("not bytes")
+
+
+def test_bytes_roundtrip():
+    test_data = np.arange(24, dtype=np.float32).reshape(4, 6)
+
+    writer = omfilesrspy.OmFilePyWriter.in_memory()
+    with pytest.raises(RuntimeError):
+        writer.to_bytes()
+    units = writer.write_scalar("K", name="units")
+    writer.write_array(test_data, chunks=[2, 3], name="temperature", children=[units], verify=True)
+    data = writer.to_bytes()
+    assert isinstance(data, bytes)
+    assert writer.closed
+    assert writer.to_bytes() == data
+
+    reader = omfilesrspy.OmFilePyReader.from_bytes(data)
+    assert reader.variable_name() == "temperature"
+    assert reader.attrs == {"units": "K"}
+    np.testing.assert_array_equal(reader[:, :], test_data)
+
+    temp_file = "test_bytes_roundtrip.om"
+    try:
+        file_writer = omfilesrspy.OmFilePyWriter(temp_file)
+        file_writer.write_array(test_data, chunks=[2, 3])
+        with pytest.raises(ValueError):
+            file_writer.to_bytes()
+        file_writer.close()
+    finally:
+        os.remove(temp_file)
+
+
+def test_in_memory_get_bytes():
+    test_data = np.arange(20, dtype=np.int32).reshape(4, 5)
+
+    with omfilesrspy.OmFilePyWriter.in_memory() as writer:
+        writer.write_array(test_data, chunks=[2, 5], name="counts")
+        with pytest.raises(ValueError):
+            writer.get_bytes()
+    data = writer.get_bytes()
+
+    reader = omfilesrspy.OmFilePyReader.from_bytes(data)
+    assert reader.variable_name() == "counts"
+    np.testing.assert_array_equal(reader[:, :], test_data)
+
+
+def test_read_into_torch():
+    torch = pytest.importorskip("torch")
+    temp_file = "test_read_into_torch.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        tensor = torch.empty((2, 3), dtype=torch.float32)
+        reader.read_into_torch(tensor, (slice(1, 3), slice(0, 3)))
+        np.testing.assert_array_equal(tensor.numpy(), test_data[1:3, 0:3])
+
+        with pytest.raises(TypeError):
+            reader.read_into_torch(torch.empty((2, 3), dtype=torch.float64), (slice(1, 3), slice(0, 3)))
+
+        with pytest.raises(ValueError):
+            reader.read_into_torch(torch.empty((3, 2), dtype=torch.float32).t(), (slice(1, 3), slice(0, 3)))
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_verify():
+    temp_file = "test_write_verify.om"
+    test_data = np.random.rand(10, 10).astype(np.float32)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(test_data, chunks=[5, 5], scale_factor=10000.0, verify=True)
+        writer.write_array(test_data, chunks=[5, 5], compression="fpx_xor_2d", verify=True)
+        writer.write_array(np.arange(100, dtype=np.int64).reshape(10, 10), chunks=[5, 5], verify=True)
+        del writer
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_as_lazy():
+    temp_file = "test_as_lazy.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        lazy = reader.as_lazy()
+
+        assert lazy.shape == (5, 5)
+        assert lazy.ndim == 2
+        assert lazy.dtype == np.float32
+        assert len(lazy) == 5
+        np.testing.assert_array_equal(lazy[1:3, :], test_data[1:3, :])
+        with pytest.raises(TypeError):
+            lazy["missing"]
+        del lazy
+        del reader
+
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        count = writer.write_scalar(42, name="count")
+        writer.write_array(test_data, chunks=[5, 5], children=[count])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        with pytest.raises(TypeError):
+            reader.as_lazy()["count"]
+        scalar = reader.init_from_offset_size(count.offset, count.size)
+        with pytest.raises(TypeError):
+            len(scalar.as_lazy())
+        del scalar
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_format_details():
+    temp_file = "test_format_details.om"
+    future_file = "test_format_details_future.om"
+
+    try:
+        create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.format_details() == {"version": 3, "layout": "trailer"}
+        del reader
+
+        with open(temp_file, "rb") as f:
+            content = bytearray(f.read())
+        content[2] = 4
+        with open(future_file, "wb") as f:
+            f.write(content)
+        with pytest.raises(omfilesrspy.OmUnsupportedVersionError, match="version 4"):
+            omfilesrspy.OmFilePyReader(future_file)
+
+    finally:
+        for path in (temp_file, future_file):
+            if os.path.exists(path):
+                os.remove(path)
+
+
+def test_isel():
+    temp_file = "test_isel.om"
+    test_data = np.arange(60, dtype=np.float32).reshape(3, 4, 5)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        writer.write_array(test_data, chunks=[1, 4, 5], dimensions=["time", "lat", "lon"])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.dimension_names() == ["time", "lat", "lon"]
+        np.testing.assert_array_equal(reader.isel({"time": slice(0, 2), "lon": 3}), test_data[0:2, :, 3])
+        np.testing.assert_array_equal(reader.isel({}), test_data)
+
+        with pytest.raises(ValueError, match="level"):
+            reader.isel({"level": 0})
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_histogram():
+    temp_file = "test_histogram.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        counts, edges = reader.histogram((slice(None), slice(None)), bins=5)
+        expected_counts, expected_edges = np.histogram(test_data, bins=5)
+        np.testing.assert_array_equal(counts, expected_counts)
+        np.testing.assert_allclose(edges, expected_edges)
+
+        counts, edges = reader.histogram((slice(None), slice(None)), bins=2, range=(0.0, 10.0))
+        np.testing.assert_array_equal(counts, [5, 6])
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_histogram_integer_edge_cases():
+    temp_file = "test_histogram_integer.om"
+    extremes = np.array([np.iinfo(np.int64).min, 0, np.iinfo(np.int64).max], dtype=np.int64)
+    small = np.arange(6, dtype=np.int32)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        extremes_var = writer.write_array(extremes, chunks=[3], compression="none", name="extremes")
+        small_var = writer.write_array(small, chunks=[6], name="small")
+        writer.write_group("root", children=[extremes_var, small_var])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        counts, _ = reader["extremes"].histogram(slice(None))
+        assert len(counts) == 10
+        assert counts.sum() == 3
+
+        # Values above the upper edge of a given range are not counted
+        counts, edges = reader["small"].histogram(slice(None), range=(0.0, 3.0))
+        np.testing.assert_array_equal(counts, [1, 1, 2])
+        np.testing.assert_array_equal(edges, [0.0, 1.0, 2.0, 3.0])
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_fill_value():
+    temp_file = "test_fill_value.om"
+    data = np.arange(20, dtype=np.int16).reshape(4, 5)
+    data[3, :] = -999
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        filled = writer.write_array(data, chunks=[2, 5], name="filled", fill_value=-999)
+        plain = writer.write_array(data, chunks=[2, 5], name="plain")
+        writer.write_group("root", children=[filled, plain])
+        with pytest.raises(ValueError):
+            writer.write_array(data, chunks=[2, 5], fill_value=1.5)
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader["filled"].fill_value == -999
+        assert reader["plain"].fill_value is None
+        assert reader["filled"].attrs == {}
+        np.testing.assert_array_equal(reader["filled"][:, :], data)
+
+        masked = reader["filled"].read_masked((slice(2, 4), slice(None)))
+        np.testing.assert_array_equal(masked.mask, data[2:4] == -999)
+        assert not reader["plain"].read_masked((slice(2, 4), slice(None))).mask.any()
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_reduce_minmax():
+    temp_file = "test_reduce_minmax.om"
+    data = np.random.default_rng(0).normal(size=(8, 9)).astype(np.float64)
+    data[2, 3] = np.nan
+    data[:, 0] = np.nan
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        values = writer.write_array(data, chunks=[3, 4], name="values")
+        ints = writer.write_array(np.arange(4, dtype=np.int32), chunks=[4], name="ints")
+        writer.write_group("root", children=[values, ints])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        for index in [(slice(None), slice(None)), (slice(1, 5), slice(2, 9, 2))]:
+            expected = reader["values"][index]
+            minimum, maximum, mean = reader["values"].reduce_minmax(index)
+            assert minimum == np.nanmin(expected)
+            assert maximum == np.nanmax(expected)
+            assert mean == pytest.approx(np.nanmean(expected))
+
+        assert all(np.isnan(reader["values"].reduce_minmax((slice(None), 0))))
+        with pytest.raises(ValueError):
+            reader["ints"].reduce_minmax(slice(None))
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_get_scalar():
+    temp_file = "test_get_scalar.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        count = writer.write_scalar(42, name="count")
+        nested = writer.write_array(np.zeros(2, dtype=np.int32), chunks=[2], name="nested")
+        writer.write_array(np.zeros((2, 2), dtype=np.float32), chunks=[2, 2], children=[count, nested])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        scalar = reader.init_from_offset_size(count.offset, count.size)
+        assert scalar.get_scalar() == 42
+        with pytest.raises(ValueError):
+            reader.get_scalar()
+        assert reader.get_scalar("count") == 42
+        with pytest.raises(TypeError):
+            reader.get_scalar("nested")
+        with pytest.raises(KeyError):
+            reader.get_scalar("missing")
+        del scalar
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_iterate_children():
+    temp_file = "test_iterate_children.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        temperature = writer.write_array(np.zeros((2, 3), dtype=np.float32), chunks=[2, 3], name="temperature")
+        writer.write_array(np.zeros((4,), dtype=np.float32), chunks=[4], name="root", children=[units, temperature])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert len(reader) == 2
+        children = [(child.variable_name(), child.shape) for child in reader]
+        assert children == [("units", []), ("temperature", [2, 3])]
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_child_by_name():
+    temp_file = "test_child_by_name.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        temperature = writer.write_array(np.ones((2, 3), dtype=np.float32), chunks=[2, 3], name="temperature")
+        writer.write_array(np.zeros((4,), dtype=np.float32), chunks=[4], name="root", children=[units, temperature])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        child = reader["temperature"]
+        assert child.shape == [2, 3]
+        np.testing.assert_array_equal(child[:, :], np.ones((2, 3), dtype=np.float32))
+        np.testing.assert_array_equal(reader[0:2], np.zeros((2,), dtype=np.float32))
+
+        with pytest.raises(KeyError):
+            reader["humidity"]
+        with pytest.raises(ValueError):
+            reader["units"]["anything"]
+        del child
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_strided():
+    temp_file = "test_read_strided.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(7, 9))
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        np.testing.assert_array_equal(reader[::2, :], test_data[::2, :])
+        np.testing.assert_array_equal(reader[:, ::2], test_data[:, ::2])
+        np.testing.assert_array_equal(reader[1::3, 2:8:4], test_data[1::3, 2:8:4])
+        np.testing.assert_array_equal(reader[::1, :], test_data)
+        with pytest.raises(NotImplementedError):
+            reader[::-1, :]
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_ellipsis():
+    temp_file = "test_read_ellipsis.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(3, 4, 5))
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        np.testing.assert_array_equal(reader[..., 2], test_data[..., 2])
+        np.testing.assert_array_equal(reader[1, ...], test_data[1, ...])
+        np.testing.assert_array_equal(reader[1, ..., 2], test_data[1, ..., 2])
+        with pytest.raises(IndexError):
+            reader[..., 1, ...]
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_shape_matches_numpy():
+    temp_file = "test_read_shape_numpy.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(4, 5, 6))
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        for index in [
+            (0, slice(None), slice(None)),
+            (slice(0, 1), slice(None), 2),
+            (1, slice(2, 3), 4),
+            (1, 2, 3),
+            (slice(None), slice(1, 2), slice(3, 4)),
+            (slice(1, 3),),
+        ]:
+            data = reader[index]
+            assert data.shape == test_data[index].shape
+            np.testing.assert_array_equal(data, test_data[index])
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_float16_roundtrip():
+    temp_file = "test_float16.om"
+    test_data = (np.random.rand(6, 7) * 100).astype(np.float16)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        writer.write_array(test_data, chunks=[3, 4], compression="fpx_xor_2d", children=[units])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.dtype() == "float16"
+        assert reader.attrs == {"units": "K"}
+        data = reader[:, :]
+        assert data.dtype == np.float16
+        np.testing.assert_array_equal(data, test_data)
+        assert reader[1:3, 2].dtype == np.float16
+        assert reader[0:0, :].dtype == np.float16
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_single_element():
+    temp_file = "test_read_single_element.om"
+
+    try:
+        for dtype in [np.float32, np.int16, np.uint64]:
+            test_data = np.arange(12).reshape(3, 4).astype(dtype)
+            writer = omfilesrspy.OmFilePyWriter(temp_file)
+            writer.write_array(test_data, chunks=[2, 2])
+            del writer
+
+            reader = omfilesrspy.OmFilePyReader(temp_file)
+            value = reader[1, 1]
+            assert isinstance(value, np.ndarray)
+            assert value.shape == ()
+            assert value.dtype == dtype
+            assert value.item() == test_data[1, 1]
+            del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_into_offset():
+    temp_file = "test_read_into_offset.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        out = np.full((8, 8), -1, dtype=np.float32)
+        reader.read_into(out, (slice(0, 2), slice(1, 4)), into_offset=[3, 5])
+        np.testing.assert_array_equal(out[3:5, 5:8], test_data[0:2, 1:4])
+        assert (out[0:3, :] == -1).all()
+        assert (out[:, 0:5] == -1).all()
+
+        with pytest.raises(IndexError):
+            reader.read_into(out, (slice(0, 2), slice(1, 4)), into_offset=[7, 0])
+        with pytest.raises(IndexError):
+            reader.read_into(out, (slice(0, 2), slice(1, 4)), into_offset=[0])
+        with pytest.raises(IndexError):
+            reader.read_into(out, (slice(0, 2), slice(1, 4)), into_offset=[2**64 - 1, 0])
+        with pytest.raises(TypeError):
+            reader.read_into(np.zeros((5, 5), dtype=np.bool_), (slice(None), slice(None)))
+        with pytest.raises(ValueError):
+            reader.read_into(np.zeros((5, 10), dtype=np.float32)[:, ::2], (slice(None), slice(None)))
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_io_hints():
+    temp_file = "test_io_hints.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(20, 20))
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        expected = reader[2:17, 5:9]
+
+        reader.set_io_hints(io_size_max=1 << 20, io_size_merge=1 << 16)
+        np.testing.assert_array_equal(reader[2:17, 5:9], expected)
+        reader.set_io_hints(io_size_max=64, io_size_merge=0)
+        np.testing.assert_array_equal(reader[2:17, 5:9], expected)
+        np.testing.assert_array_equal(reader[:, :], test_data)
+        reader.set_io_hints()
+        np.testing.assert_array_equal(reader[2:17, 5:9], expected)
+        with pytest.raises(ValueError):
+            reader.set_io_hints(io_size_max=0)
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_ranges():
+    temp_file = "test_read_ranges.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(4, 5, 6))
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        indices = [(t, slice(None), slice(None)) for t in [3, 0, 2]] + [(slice(1, 3), 4, slice(None, None, 2))]
+        results = reader.read_ranges(indices)
+        assert len(results) == len(indices)
+        for index, result in zip(indices, results):
+            np.testing.assert_array_equal(result, reader[index])
+            np.testing.assert_array_equal(result, test_data[index])
+
+        with pytest.raises(IndexError):
+            reader.read_ranges([(0, slice(None), slice(None)), (4, slice(None), slice(None))])
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_attrs():
+    temp_file = "test_attrs.om"
+    test_data = np.arange(12, dtype=np.int32).reshape(3, 4)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("mm", name="units")
+        level = writer.write_scalar(850, name="level")
+        writer.write_array(test_data, chunks=[3, 4], name="precipitation", children=[units, level], dimensions=["y", "x"])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.attrs == {"units": "mm", "level": 850}
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_default_chunks():
+    temp_file = "test_write_default_chunks.om"
+
+    try:
+        for shape, expected_chunks in [((1000,), [1000]), ((720, 1440), [45, 1440]), ((3, 50, 70), [3, 50, 70])]:
+            test_data = np.arange(np.prod(shape), dtype=np.float32).reshape(shape)
+            writer = omfilesrspy.OmFilePyWriter(temp_file)
+            variable = writer.write_array(test_data)
+            del writer
+            assert variable.chunks == expected_chunks
+
+            reader = omfilesrspy.OmFilePyReader(temp_file)
+            assert reader.chunk_dimensions == expected_chunks
+            np.testing.assert_array_equal(reader[...], test_data)
+            del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_atomic_write():
+    temp_file = "test_atomic_write.om"
+    temp_path = f"{temp_file}.tmp.{os.getpid()}"
+    test_data = np.arange(25, dtype=np.float32).reshape(5, 5)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file, atomic=True)
+        writer.write_array(test_data, chunks=[5, 5])
+        assert not os.path.exists(temp_file)
+        assert os.path.exists(temp_path)
+
+        writer.close()
+        writer.close()
+        assert os.path.exists(temp_file)
+        assert not os.path.exists(temp_path)
+        with pytest.raises(ValueError):
+            writer.write_array(test_data, chunks=[5, 5])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        np.testing.assert_array_equal(reader[:, :], test_data)
+        del reader
+
+    finally:
+        for path in [temp_file, temp_path]:
+            if os.path.exists(path):
+                os.remove(path)
+
+
+def test_info():
+    temp_file = "test_info.om"
+    test_data = np.arange(20, dtype=np.float32).reshape(4, 5)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        writer.write_array(test_data, chunks=[2, 5], scale_factor=100.0, name="temperature", children=[units])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        info = reader.info()
+        record_size = info.pop("record_size")
+        assert record_size == reader.get_flat_variable_metadata()["temperature"][1]
+        assert info == {
+            "name": "temperature",
+            "kind": "array",
+            "shape": [4, 5],
+            "num_children": 1,
+            "dtype": "float32",
+            "size": 20,
+            "compression": "pfor_delta_2d",
+            "scale_factor": 100.0,
+            "add_offset": 0.0,
+            "chunk_dimensions": [2, 5],
+        }
+        json.dumps(info)
+
+        scalar_info = reader["units"].info()
+        assert scalar_info["kind"] == "scalar"
+        assert scalar_info["compression"] is None
+        assert scalar_info["size"] is None
+        assert scalar_info["record_size"] == reader.get_flat_variable_metadata()["temperature/units"][1]
+        del reader
+
+        # The record size does not depend on the amount of data
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        large_data = np.random.rand(400, 500).astype(np.float32)
+        writer.write_array(large_data, chunks=[20, 50], scale_factor=100.0, name="temperature", children=[units])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.info()["record_size"] == record_size
+        assert record_size < os.path.getsize(temp_file) // 100
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_writer_context_manager():
+    temp_file = "test_writer_context_manager.om"
+    test_data = np.arange(25, dtype=np.float32).reshape(5, 5)
+
+    try:
+        with omfilesrspy.OmFilePyWriter(temp_file, atomic=True) as writer:
+            writer.write_array(test_data, chunks=[5, 5])
+        with pytest.raises(ValueError):
+            writer.write_array(test_data, chunks=[5, 5])
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        np.testing.assert_array_equal(reader[:, :], test_data)
+        del reader
+
+        with pytest.raises(RuntimeError):
+            with omfilesrspy.OmFilePyWriter(temp_file) as writer:
+                writer.write_scalar("K", name="units")
+
+        with pytest.raises(KeyError):
+            with omfilesrspy.OmFilePyWriter(temp_file) as writer:
+                raise KeyError("failed")
+
+    finally:
+        os.remove(temp_file)
+
+
+class CountingReader(io.BytesIO):
+    def __init__(self, data):
+        super().__init__(data)
+        self.read_calls = 0
+
+    def read(self, size=-1):
+        self.read_calls += 1
+        return super().read(size)
+
+
+def test_fsspec_block_cache():
+    temp_file = "test_fsspec_block_cache.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(20, 20))
+        with open(temp_file, "rb") as f:
+            content = f.read()
+    finally:
+        os.remove(temp_file)
+
+    read_calls = {}
+    for cache_size in [0, 1 << 20]:
+        file_obj = CountingReader(content)
+        reader = omfilesrspy.OmFilePyReader.from_reader(file_obj, cache_size=cache_size)
+        for _ in range(3):
+            np.testing.assert_array_equal(reader[:, :], test_data)
+            np.testing.assert_array_equal(reader[3:7, 12:18], test_data[3:7, 12:18])
+        read_calls[cache_size] = file_obj.read_calls
+
+        stats = reader.cache_stats()
+        if cache_size == 0:
+            assert stats == {"hits": 0, "misses": 0, "entries": 0, "bytes": 0}
+        else:
+            assert stats["hits"] > 0
+            assert stats["bytes"] == len(content)
+            reader.release_prefetch()
+            assert reader.cache_stats()["entries"] == 0
+            np.testing.assert_array_equal(reader[:, :], test_data)
+        del reader
+
+    # The whole file fits into one block, so it is read only once
+    assert read_calls[1 << 20] == 1
+    assert read_calls[0] > read_calls[1 << 20]
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="open files cannot be deleted on Windows")
+def test_was_deleted():
+    temp_file = "test_was_deleted.om"
+    _, test_data = create_test_om_file(temp_file)
+
+    reader = omfilesrspy.OmFilePyReader(temp_file)
+    np.testing.assert_array_equal(reader[:, :], test_data)
+    assert not reader.was_deleted()
+    os.remove(temp_file)
+    assert reader.was_deleted()
+    np.testing.assert_array_equal(reader[:, :], test_data)
+    del reader
+
+    writer = omfilesrspy.OmFilePyWriter.in_memory()
+    writer.write_array(test_data, chunks=[5, 5])
+    assert not omfilesrspy.OmFilePyReader.from_bytes(writer.to_bytes()).was_deleted()
+
+
+def test_with_cache():
+    temp_file = "test_with_cache.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(20, 20))
+        with open(temp_file, "rb") as f:
+            file_obj = CountingReader(f.read())
+    finally:
+        os.remove(temp_file)
+
+    reader = omfilesrspy.OmFilePyReader.from_reader(file_obj, cache_size=0).with_cache(1 << 20)
+    np.testing.assert_array_equal(reader[3:7, 12:18], test_data[3:7, 12:18])
+    read_calls = file_obj.read_calls
+    stats = reader.cache_stats()
+    assert stats["misses"] > 0 and stats["entries"] > 0
+
+    np.testing.assert_array_equal(reader[3:7, 12:18], test_data[3:7, 12:18])
+    assert file_obj.read_calls == read_calls
+    assert reader.cache_stats()["hits"] > stats["hits"]
+    del reader
+
+
+def test_open_path():
+    temp_file = "test_open_path.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        temperature = writer.write_array(
+            np.ones((2, 3), dtype=np.float32), chunks=[2, 3], name="temperature", children=[units]
+        )
+        surface = writer.write_array(np.zeros((2,), dtype=np.float32), chunks=[2], name="surface", children=[temperature])
+        writer.write_array(np.zeros((1,), dtype=np.float32), chunks=[1], name="root", children=[surface])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        temperature = reader.open_path("surface/temperature")
+        np.testing.assert_array_equal(temperature[:, :], np.ones((2, 3), dtype=np.float32))
+        assert reader.open_path("/surface/temperature/units").get_scalar() == "K"
+
+        for path in ["surface/humidity", "missing/temperature", ""]:
+            with pytest.raises(KeyError):
+                reader.open_path(path)
+        del temperature
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_reader_repr():
+    temp_file = "test_reader_repr.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        writer.write_array(
+            np.zeros((4, 5), dtype=np.float32), chunks=[2, 5], scale_factor=100.0, name="temperature", children=[units]
+        )
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert repr(reader) == (
+            "OmFilePyReader(name='temperature', shape=[4, 5], dtype=float32, compression='pfor_delta_2d', children=1)"
+        )
+        assert repr(reader["units"]) == "OmFilePyReader(name='units', value='K', children=0)"
+
+        text = str(reader)
+        for expected in [
+            "'temperature' (array)",
+            "dtype: float32",
+            "shape: [4, 5]",
+            "chunks: [2, 5]",
+            "compression: pfor_delta_2d (scale_factor=100, add_offset=0)",
+            "children: 1",
+        ]:
+            assert expected in text
+
+        units_text = str(reader["units"])
+        assert "'units' (scalar)" in units_text
+        assert "value: 'K'" in units_text
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_group():
+    temp_file = "test_write_group.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        temperature = writer.write_array(np.ones((2, 3), dtype=np.float32), chunks=[2, 3], name="temperature")
+        empty = writer.write_group("empty")
+        surface = writer.write_group("surface", children=[temperature, empty])
+        writer.write_group("root", children=[surface])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.is_group
+        assert repr(reader) == "OmFilePyReader(name='root', group, children=1)"
+        assert reader.shape == []
+        assert len(reader) == 1
+
+        surface = reader["surface"]
+        assert surface.is_group
+        assert len(surface) == 2
+        assert [child.variable_name() for child in surface] == ["temperature", "empty"]
+        assert len(surface["empty"]) == 0
+        assert not surface["temperature"].is_group
+        np.testing.assert_array_equal(surface["temperature"][:, :], np.ones((2, 3), dtype=np.float32))
+        del surface
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_get_child_by_name():
+    temp_file = "test_get_child_by_name.om"
+    child1_data = np.arange(6, dtype=np.float32).reshape(2, 3)
+    child2_data = np.arange(4, dtype=np.int32)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        child1 = writer.write_array(child1_data, chunks=[2, 3], name="child1")
+        child2 = writer.write_array(child2_data, chunks=[4], name="child2")
+        duplicate = writer.write_scalar("second", name="child2")
+        writer.write_group("root", children=[child1, child2, duplicate])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        np.testing.assert_array_equal(reader.get_child_by_name("child1")[:, :], child1_data)
+        np.testing.assert_array_equal(reader.get_child_by_name("child2")[:], child2_data)
+        assert reader.get_child_by_name("missing") is None
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_list_children():
+    temp_file = "test_list_children.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        temperature = writer.write_array(
+            np.ones((2, 3), dtype=np.float32), chunks=[2, 3], name="temperature", children=[units]
+        )
+        level = writer.write_scalar(850, name="level")
+        surface = writer.write_group("surface", children=[temperature])
+        writer.write_array(np.zeros((4,), dtype=np.int16), chunks=[4], name="root", children=[surface, level])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.list_children() == [("surface", "group"), ("level", "int64")]
+        assert reader["surface"].list_children() == [("temperature", "float32")]
+        assert reader["surface"]["temperature"].list_children() == [("units", "str")]
+
+        children = reader.children
+        assert [child.variable_name() for child in children] == ["surface", "level"]
+        assert children[0].children[0].variable_name() == "temperature"
+        np.testing.assert_array_equal(children[0].children[0][:, :], np.ones((2, 3), dtype=np.float32))
+        assert children[1].children == []
+        del reader, children
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_read_all_scalars():
+    temp_file = "test_read_all_scalars.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        level = writer.write_scalar(850, name="level")
+        model = writer.write_group("model", children=[units, level])
+        resolution = writer.write_scalar(0.25, name="resolution")
+        writer.write_group("config", children=[model, resolution])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        scalars = reader.read_all_scalars()
+        assert scalars == {"config/model/units": "K", "config/model/level": 850, "config/resolution": 0.25}
+        assert isinstance(scalars["config/model/level"], int)
+        assert reader["model"].read_all_scalars() == {"model/units": "K", "model/level": 850}
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_to_tree():
+    temp_file = "test_to_tree.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        temperature = writer.write_array(
+            np.zeros((4, 6), dtype=np.float32), chunks=[2, 3], name="temperature", children=[units]
+        )
+        level = writer.write_scalar(850, name="level")
+        surface = writer.write_group("surface", children=[temperature])
+        writer.write_group("root", children=[surface, level])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.to_tree() == {
+            "root": {
+                "dtype": "group",
+                "shape": [],
+                "children": {
+                    "surface": {
+                        "dtype": "group",
+                        "shape": [],
+                        "children": {
+                            "temperature": {
+                                "dtype": "float32",
+                                "shape": [4, 6],
+                                "children": {"units": {"dtype": "str", "shape": [], "children": {}}},
+                            }
+                        },
+                    },
+                    "level": {"dtype": "int64", "shape": [], "children": {}},
+                },
+            }
+        }
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_array_with_attrs():
+    temp_file = "test_write_array_with_attrs.om"
+    test_data = np.arange(12, dtype=np.float32).reshape(3, 4)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        with pytest.raises(TypeError):
+            writer.write_array_with_attrs(test_data, {"invalid": [1, 2]}, chunks=[3, 4])
+        writer.write_array_with_attrs(
+            test_data, {"units": "K", "level": 850, "scale": 0.5}, chunks=[3, 4], name="temperature"
+        )
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.variable_name() == "temperature"
+        assert reader.attrs == {"units": "K", "level": 850, "scale": 0.5}
+        np.testing.assert_array_equal(reader[:, :], test_data)
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_describe():
+    temp_file = "test_describe.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        temperature = writer.write_array(
+            np.zeros((4, 6), dtype=np.float32),
+            chunks=[2, 3],
+            compression="pfor_delta_2d_int16",
+            name="temperature",
+            children=[units],
+        )
+        pressure = writer.write_array(np.zeros((4,), dtype=np.int32), chunks=[4], name="pressure")
+        writer.write_group("root", children=[temperature, pressure])
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.describe() == "\n".join(
+            [
+                "root (group)",
+                "  temperature (float32, shape=[4, 6], chunks=[2, 3], pfor_delta_2d_int16)",
+                "    units (scalar, 'K')",
+                "  pressure (int32, shape=[4], chunks=[4], pfor_delta_2d)",
+            ]
+        )
+        assert reader["temperature"].describe().splitlines()[0].startswith("temperature (float32")
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_flat_variable_info():
+    temp_file = "test_flat_variable_info.om"
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        units = writer.write_scalar("K", name="units")
+        writer.write_array(
+            np.zeros((4, 5), dtype=np.float32),
+            chunks=[2, 5],
+            scale_factor=100.0,
+            compression="pfor_delta_2d_int16",
+            name="temperature",
+            children=[units],
+        )
+        del writer
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        metadata = reader.get_flat_variable_metadata()
+        info = reader.get_flat_variable_info()
+        assert info.keys() == metadata.keys()
+
+        for path, variable in info.items():
+            assert (variable.offset, variable.size) == metadata[path]
+            if variable.name == "temperature":
+                assert variable.data_type == "float32"
+                assert variable.compression == "pfor_delta_2d_int16"
+                assert variable.scale_factor == 100.0
+                assert variable.add_offset == 0.0
+                assert variable.dimensions == [4, 5]
+                assert variable.chunk_dimensions == [2, 5]
+                assert not variable.is_scalar
+            else:
+                assert variable.name == "units"
+                assert variable.data_type == "str"
+                assert variable.compression is None
+                assert variable.chunk_dimensions is None
+                assert variable.is_scalar
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_array_from_iter():
+    temp_file = "test_write_array_from_iter.om"
+    data = np.arange(15, dtype=np.float32).reshape(5, 3)
+
+    def chunk_iter(order):
+        for i, j in order:
+            yield (i, j), data[i * 2 : i * 2 + 2, j * 2 : j * 2 + 2]
+
+    row_major = [(i, j) for i in range(3) for j in range(2)]
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        variable = writer.write_array_from_iter(chunk_iter(row_major), [5, 3], [2, 2], "float32", name="streamed")
+        assert variable.chunks == [2, 2]
+        with pytest.raises(ValueError, match="row-major"):
+            writer.write_array_from_iter(chunk_iter(row_major[::-1]), [5, 3], [2, 2], "float32")
+        with pytest.raises(ValueError, match="shape"):
+            writer.write_array_from_iter([((0, 0), data[:1, :2])], [5, 3], [2, 2], "float32")
+        with pytest.raises(ValueError, match="of the 6 chunks"):
+            writer.write_array_from_iter(chunk_iter(row_major[:4]), [5, 3], [2, 2], "float32")
+        with pytest.raises(TypeError):
+            writer.write_array_from_iter([((0, 0), data[:2, :2].astype(np.float64))], [5, 3], [2, 2], "float32")
+        writer.set_root(variable)
+        writer.close()
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.variable_name() == "streamed"
+        np.testing.assert_array_equal(reader[:, :], data)
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_rechunk_from():
+    source_file = "test_rechunk_source.om"
+    target_file = "test_rechunk_target.om"
+    test_data = np.arange(240, dtype=np.float32).reshape(4, 6, 10) / 10
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(source_file)
+        writer.write_array(
+            test_data, chunks=[1, 6, 10], scale_factor=10.0, compression="pfor_delta_2d_int16", name="temperature"
+        )
+        del writer
+
+        source = omfilesrspy.OmFilePyReader(source_file)
+        writer = omfilesrspy.OmFilePyWriter(target_file)
+        variable = writer.rechunk_from(source, [4, 2, 2])
+        del writer
+        assert variable.name == "temperature"
+        assert variable.chunks == [4, 2, 2]
+
+        target = omfilesrspy.OmFilePyReader(target_file)
+        assert target.chunk_dimensions == [4, 2, 2]
+        assert target.compression == omfilesrspy.CompressionType.PFOR_DELTA_2D_INT16
+        assert target.scale_factor == 10.0
+        np.testing.assert_array_equal(target[...], source[...])
+        del target
+        del source
+
+    finally:
+        for path in [source_file, target_file]:
+            if os.path.exists(path):
+                os.remove(path)
+
+
+def test_read_into_reuses_buffer():
+    temp_file = "test_read_into_reuse.om"
+
+    try:
+        _, test_data = create_test_om_file(temp_file, shape=(6, 5, 4))
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+
+        out = np.empty((5, 4), dtype=np.float32)
+        for t in range(6):
+            reader.read_into(out, (t, slice(None), slice(None)))
+            np.testing.assert_array_equal(out, reader[t, :, :])
+            np.testing.assert_array_equal(out, test_data[t])
+
+        out = np.empty((2, 1, 4), dtype=np.float32)
+        reader.read_into(out, (slice(1, 3), slice(2, 3), slice(None)))
+        np.testing.assert_array_equal(out, reader[1:3, 2:3, :])
+
+        with pytest.raises(ValueError):
+            reader.read_into(np.empty((4, 5), dtype=np.float32), (0, slice(None), slice(None)))
+        readonly = np.empty((5, 4), dtype=np.float32)
+        readonly.flags.writeable = False
+        with pytest.raises(ValueError):
+            reader.read_into(readonly, (0, slice(None), slice(None)))
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_writer_set_root():
+    temp_file = "test_writer_set_root.om"
+    test_data = np.arange(6, dtype=np.float32).reshape(2, 3)
+
+    try:
+        with omfilesrspy.OmFilePyWriter(temp_file) as writer:
+            assert not writer.closed
+            temperature = writer.write_array(test_data, chunks=[2, 3], name="temperature")
+            writer.write_array(np.zeros((4,), dtype=np.float32), chunks=[4], name="other")
+            writer.set_root(temperature)
+        assert writer.closed
+        with pytest.raises(ValueError):
+            writer.set_root(temperature)
+
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.variable_name() == "temperature"
+        np.testing.assert_array_equal(reader[:, :], test_data)
+        del reader
+
+        with omfilesrspy.OmFilePyWriter(temp_file) as writer:
+            units = writer.write_scalar("K", name="units")
+            writer.set_root(units)
+        reader = omfilesrspy.OmFilePyReader(temp_file)
+        assert reader.get_scalar() == "K"
+        del reader
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_offset_precision():
+    temp_file = "test_write_offset_precision.om"
+    # Epoch seconds, which are not representable as 32 bit floats
+    offset = 1_700_000_001.0
+    test_data = offset + np.arange(10, dtype=np.float64)
+    assert np.float32(offset) != offset
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        with pytest.raises(ValueError, match="32 bit float"):
+            writer.write_array(test_data, chunks=[10], add_offset=offset)
+        with pytest.raises(ValueError, match="32 bit float"):
+            writer.write_array_from_iter(
+                iter([([0], test_data)]), dimensions=[10], chunks=[10], dtype="float64", add_offset=offset
+            )
+        # Lossless compressions and coarse quantization steps are not affected
+        writer.write_array(test_data, chunks=[10], add_offset=offset, compression="fpx_xor_2d")
+        writer.write_array(test_data, chunks=[10], scale_factor=0.01, add_offset=offset)
+        writer.write_array(test_data - offset, chunks=[10], verify=True)
+        del writer
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_invalid_chunks():
+    temp_file = "test_write_invalid_chunks.om"
+    test_data = np.zeros((4, 6), dtype=np.float32)
+
+    try:
+        writer = omfilesrspy.OmFilePyWriter(temp_file)
+        with pytest.raises(ValueError, match="between 1 and the dimension 4"):
+            writer.write_array(test_data, chunks=[5, 6])
+        with pytest.raises(ValueError, match="between 1 and"):
+            writer.write_array(test_data, chunks=[2, 0])
+        with pytest.raises(ValueError, match="chunk dimensions"):
+            writer.write_array(test_data, chunks=[2])
+        writer.write_array(test_data, chunks=[4, 6])
+        del writer
+
+    finally:
+        os.remove(temp_file)
+
+
+def test_write_array_rejected_writes_no_children():
+    int_data = np.zeros((2, 3), dtype=np.int32)
+    float16_data = np.zeros((2, 3), dtype=np.float16)
+
+    reference = omfilesrspy.OmFilePyWriter.in_memory()
+    reference.write_scalar(1, name="first")
+    expected = reference.write_scalar(2, name="second")
+
+    writer = omfilesrspy.OmFilePyWriter.in_memory()
+    writer.write_scalar(1, name="first")
+    with pytest.raises(ValueError):
+        writer.write_array(int_data, chunks=[2, 3], dimensions=["x", "y"], fill_value=1.5)
+    with pytest.raises(ValueError):
+        writer.write_array(int_data, chunks=[4, 3], categories={0: "none"}, dimensions=["x", "y"])
+    with pytest.raises(ValueError):
+        writer.write_array(float16_data, chunks=[2, 3], categories={0: "none"})
+    second = writer.write_scalar(2, name="second")
+
+    # Nothing was written by the rejected calls
+    assert second.offset == expected.offset
+
+
+def test_write_non_contiguous():
+    temp_file = "test_write_non_contiguous.om"
+    data = np.arange(60, dtype=np.float32).reshape(6, 10)
+
+    try:
+        for view in [data.T, data[::2, 1::3]]:
+            assert not view.flags["C_CONTIGUOUS"]
+            writer = omfilesrspy.OmFilePyWriter(temp_file)
+            writer.write_array(view, chunks=[2, 2])
+            del writer
+
+            reader = omfilesrspy.OmFilePyReader(temp_file)
+            assert reader.shape == list(view.shape)
+            np.testing.assert_array_equal(reader[...], view)
+            del reader
+
+    finally:
+        os.remove(temp_file)
+
+
 # def test_fsspec_backend():
 #     fsspec_object = fsspec.open("test_files/read_test.om", "rb")
 
@@ -89,7 +2089,7 @@ def test_s3_reader():
 
     # Create reader over fs spec backend
     reader = omfilesrspy.OmFilePyReader(backend)
-    data = reader[57812:57813, 0:100]
+    data = reader[57812, 0:100]
 
     # Verify the data
     expected = [18.0, 17.7, 17.65, 17.45, 17.15, 17.6, 18.7, 20.75, 21.7, 22.65]
@@ -103,7 +2103,7 @@ def test_s3_reader_with_cache():
 
     # Create reader over fs spec backend
     reader = omfilesrspy.OmFilePyReader(backend)
-    data = reader[57812:57813, 0:100]
+    data = reader[57812, 0:100]
 
     # Verify the data
     expected = [18.0, 17.7, 17.65, 17.45, 17.15, 17.6, 18.7, 20.75, 21.7, 22.65]
@@ -6,7 +6,7 @@ def create_test_om_file(filename: str = "test_file.om", shape=(5, 5), dtype=np.f
     test_data = np.arange(np.prod(shape), dtype=dtype).reshape(shape)
 
     writer = OmFilePyWriter(filename)
-    writer.write_array(test_data, chunks=[5, 5])
+    writer.write_array(test_data, chunks=[min(5, dim) for dim in shape])
     del writer
 
     return filename, test_data
@@ -2,7 +2,7 @@ import os
 
 import numpy as np
 import xarray as xr
-from omfilesrspy.omfilesrspy import OmFilePyReader
+from omfilesrspy.omfilesrspy import OmFilePyReader, OmFilePyWriter
 from omfilesrspy.xarray_backend import OmBackendArray
 from xarray.core import indexing
 
@@ -66,3 +66,22 @@ def test_xarray_backend():
 
     finally:
         os.remove(temp_file)
+
+
+def test_xarray_backend_dimension_names():
+    temp_file = "test_xarray_dimension_names.om"
+    temperature = np.arange(12, dtype=np.float32).reshape(3, 4)
+
+    try:
+        writer = OmFilePyWriter(temp_file)
+        writer.write_array(temperature, chunks=[3, 4], name="temperature", dimensions=["lat", "lon"])
+        del writer
+
+        ds = xr.open_dataset(temp_file, engine="om")
+        assert list(ds.data_vars) == ["temperature"]
+        assert ds["temperature"].dims == ("lat", "lon")
+        np.testing.assert_array_equal(ds["temperature"].values, temperature)
+        del ds
+
+    finally:
+        os.remove(temp_file)